@@ -0,0 +1,179 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Day {
+    name: String,
+    number: u32,
+    module: PathBuf,
+    input: PathBuf,
+    example: PathBuf,
+}
+
+fn find_days(src: &Path, inputs: &Path) -> Vec<Day> {
+    let mut days = fs::read_dir(src)
+        .expect("Unable to read src directory")
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let number = name.strip_prefix("day")?.strip_suffix(".rs")?;
+
+            if number.len() != 2 || !number.chars().all(|c| c.is_ascii_digit()) {
+                println!(
+                    "cargo:warning=src/{} looks like a day but isn't named dayNN.rs, skipping it",
+                    name
+                );
+                return None;
+            }
+
+            let name = format!("day{}", number);
+
+            Some(Day {
+                number: number.parse().ok()?,
+                module: src.join(format!("{}.rs", name)),
+                input: inputs.join(format!("{}.txt", name)),
+                example: inputs.join("examples").join(format!("{}.txt", name)),
+                name,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    days.sort_by_key(|day| day.number);
+    days
+}
+
+// new-day creates an empty example file, treat it as missing until it's filled in
+fn has_example(day: &Day) -> bool {
+    fs::metadata(&day.example).is_ok_and(|meta| meta.len() > 0)
+}
+
+fn generate(days: &[Day]) -> String {
+    let mut code = String::new();
+    let each = |f: &dyn Fn(&Day) -> String| days.iter().map(f).collect::<String>();
+
+    write!(
+        code,
+        "{modules}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum Day {{
+{variants}}}
+
+impl Day {{
+    pub const ALL: &'static [Day] = &[{all}];
+
+    pub fn name(&self) -> &'static str {{
+        match self {{
+{names}        }}
+    }}
+
+    pub fn number(&self) -> u32 {{
+        match self {{
+{numbers}        }}
+    }}
+
+    /// Runs with the day's default options
+    pub fn run(&self, parts: &[Part], input: &str) -> anyhow::Result<solution::Run> {{
+        match self {{
+{runs}        }}
+    }}
+}}
+
+#[derive(Debug, Parser)]
+#[allow(non_camel_case_types)]
+pub enum Solutions {{
+{subcommands}}}
+
+impl Solutions {{
+    pub fn day(&self) -> Day {{
+        match self {{
+{solution_days}        }}
+    }}
+
+    pub fn part(&self) -> Part {{
+        match self {{
+{solution_parts}        }}
+    }}
+
+    /// Runs with the options given on the command line
+    pub fn run(&self, input: &str) -> anyhow::Result<solution::Run> {{
+        match self {{
+{solution_runs}        }}
+    }}
+}}
+
+fn input(day: Day) -> &'static str {{
+    match day {{
+{inputs}    }}
+}}
+
+fn example(day: Day) -> Option<&'static str> {{
+    match day {{
+{examples}    }}
+}}
+",
+        modules = each(&|d| format!("#[path = {:?}]\nmod {};\n", d.module, d.name)),
+        variants = each(&|d| format!("    {},\n", d.name)),
+        all = days
+            .iter()
+            .map(|d| format!("Day::{}", d.name))
+            .collect::<Vec<_>>()
+            .join(", "),
+        names = each(&|d| format!("            Day::{0} => {0:?},\n", d.name)),
+        numbers = each(&|d| format!("            Day::{} => {},\n", d.name, d.number)),
+        runs = each(&|d| format!(
+            "            Day::{0} => solution::run::<crate::{0}::Solver>(parts, input, &Default::default()),\n",
+            d.name
+        )),
+        subcommands = each(&|d| format!(
+            "    {0} {{\n        #[clap(flatten)]\n        options: <crate::{0}::Solver as Solution>::Options,\n        #[clap(subcommand)]\n        part: Part,\n    }},\n",
+            d.name
+        )),
+        solution_days = each(&|d| format!("            Self::{0} {{ .. }} => Day::{0},\n", d.name)),
+        solution_parts = each(&|d| format!("            Self::{} {{ part, .. }} => *part,\n", d.name)),
+        solution_runs = each(&|d| format!(
+            "            Self::{0} {{ part, options }} => solution::run::<crate::{0}::Solver>(&[*part], input, options),\n",
+            d.name
+        )),
+        inputs = each(&|d| format!("        Day::{} => include_str!({:?}),\n", d.name, d.input)),
+        examples = each(&|d| if has_example(d) {
+            format!(
+                "        Day::{} => Some(include_str!({:?})),\n",
+                d.name, d.example
+            )
+        } else {
+            format!("        Day::{} => None,\n", d.name)
+        }),
+    )
+    .unwrap();
+
+    code
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=inputs/examples");
+
+    let manifest_dir =
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
+    let days = find_days(&manifest_dir.join("src"), &manifest_dir.join("inputs"));
+
+    if days.is_empty() {
+        panic!("No dayNN.rs modules found in src/");
+    }
+
+    for day in &days {
+        if !day.input.exists() {
+            panic!(
+                "{} has no input file, expected {}",
+                day.name,
+                day.input.display()
+            );
+        }
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+
+    fs::write(Path::new(&out_dir).join("days.rs"), generate(&days))
+        .expect("Unable to write days.rs");
+}
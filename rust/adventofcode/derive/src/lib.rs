@@ -0,0 +1,352 @@
+//! `#[derive(PuzzleParse)]` for the adventofcode binary.
+//!
+//! The generated code refers to `crate::parse`, so it only works inside that crate.
+
+use std::collections::HashSet;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Lit, LitStr, Member, Meta};
+
+/// Generates `FromSpan` and `FromStr` from a `#[pattern = "..."]` attribute.
+///
+/// Placeholders name a field, `{x1}`, or a tuple field, `{0}`, and are parsed with
+/// the field's `FromStr`. `{start.x}` assigns inside a field, which needs the type
+/// to implement `Default`. Enums put a pattern on each variant and the first
+/// variant whose pattern fits the text is used. Use `{{` and `}}` for literal braces.
+///
+/// ```ignore
+/// #[derive(PuzzleParse)]
+/// #[pattern = "{x1},{y1} -> {x2},{y2}"]
+/// struct Line {
+///     x1: i64,
+///     y1: i64,
+///     x2: i64,
+///     y2: i64,
+/// }
+/// ```
+#[proc_macro_derive(PuzzleParse, attributes(pattern))]
+pub fn derive_puzzle_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Placeholder {
+    field: String,
+    path: Vec<String>,
+}
+
+/// The text around the placeholders, so there's always one more literal than
+/// there are placeholders
+#[derive(Debug, PartialEq, Eq)]
+struct Pattern {
+    literals: Vec<String>,
+    placeholders: Vec<Placeholder>,
+}
+
+fn parse_pattern(pattern: &str) -> Result<Pattern, String> {
+    let mut literals = vec![String::new()];
+    let mut placeholders = Vec::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literals.last_mut().unwrap().push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literals.last_mut().unwrap().push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("Unterminated placeholder '{{{}'", name)),
+                    }
+                }
+
+                let mut parts = name.split('.').map(str::trim).map(String::from);
+                let field = parts.next().unwrap_or_default();
+                let path = parts.collect::<Vec<_>>();
+
+                if field.is_empty() || path.iter().any(String::is_empty) {
+                    return Err(format!("Invalid placeholder '{{{}}}'", name));
+                }
+                if !placeholders.is_empty() && literals.last().unwrap().is_empty() {
+                    return Err(format!(
+                        "Placeholder '{{{}}}' needs some text between it and the previous one",
+                        name
+                    ));
+                }
+
+                placeholders.push(Placeholder { field, path });
+                literals.push(String::new());
+            }
+            '}' => return Err(String::from("Unmatched '}', use '}}' for a literal brace")),
+            c => literals.last_mut().unwrap().push(c),
+        }
+    }
+
+    Ok(Pattern {
+        literals,
+        placeholders,
+    })
+}
+
+fn pattern_attr(attrs: &[Attribute]) -> Option<syn::Result<LitStr>> {
+    let attr = attrs.iter().find(|attr| attr.path.is_ident("pattern"))?;
+
+    Some(match attr.parse_meta() {
+        Ok(Meta::NameValue(meta)) => match meta.lit {
+            Lit::Str(lit) => Ok(lit),
+            lit => Err(syn::Error::new_spanned(lit, "Expected a string")),
+        },
+        _ => Err(syn::Error::new_spanned(
+            attr,
+            "Expected #[pattern = \"...\"]",
+        )),
+    })
+}
+
+fn member(name: &str, lit: &LitStr) -> syn::Result<Member> {
+    syn::parse_str(name)
+        .map_err(|_| syn::Error::new(lit.span(), format!("'{}' isn't a field name", name)))
+}
+
+/// An expression building `constructor` from the placeholders, with `fields`
+/// holding the matched spans
+fn construct(
+    constructor: TokenStream2,
+    fields: &Fields,
+    pattern: &Pattern,
+    lit: &LitStr,
+    allow_default: bool,
+) -> syn::Result<TokenStream2> {
+    let members = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => i.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut seen = HashSet::new();
+
+    for placeholder in &pattern.placeholders {
+        if !members.contains(&placeholder.field) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("There's no field '{}'", placeholder.field),
+            ));
+        }
+        if placeholder.path.is_empty() && !seen.insert(&placeholder.field) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("'{}' is in the pattern more than once", placeholder.field),
+            ));
+        }
+    }
+
+    let values = (0..pattern.placeholders.len())
+        .map(|i| quote!(fields[#i].parse()?))
+        .collect::<Vec<_>>();
+
+    if pattern.placeholders.iter().all(|p| p.path.is_empty()) {
+        if let Some(missing) = members.iter().find(|m| !seen.contains(m)) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("'{}' is missing from the pattern", missing),
+            ));
+        }
+
+        let names = pattern
+            .placeholders
+            .iter()
+            .map(|p| member(&p.field, lit))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        return Ok(quote!(#constructor { #(#names: #values),* }));
+    }
+
+    if !allow_default {
+        return Err(syn::Error::new(
+            lit.span(),
+            "Placeholders like '{a.b}' are only supported on structs",
+        ));
+    }
+
+    let targets = pattern
+        .placeholders
+        .iter()
+        .map(|p| {
+            let path = std::iter::once(&p.field)
+                .chain(p.path.iter())
+                .map(|name| member(name, lit))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            Ok(quote!(value #(.#path)*))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote!({
+        let mut value = <Self as ::std::default::Default>::default();
+        #(#targets = #values;)*
+        value
+    }))
+}
+
+fn parsed_pattern(lit: &LitStr) -> syn::Result<Pattern> {
+    parse_pattern(&lit.value()).map_err(|e| syn::Error::new(lit.span(), e))
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let lit = pattern_attr(&input.attrs).unwrap_or_else(|| {
+                Err(syn::Error::new_spanned(
+                    name,
+                    "PuzzleParse needs a #[pattern = \"...\"] attribute",
+                ))
+            })?;
+            let pattern = parsed_pattern(&lit)?;
+            let literals = &pattern.literals;
+            let value = construct(quote!(Self), &data.fields, &pattern, &lit, true)?;
+
+            quote! {
+                let fields = span.split_pattern(&[#(#literals),*]).ok_or_else(|| {
+                    span.error(::anyhow::anyhow!("Expected '{}'", #lit))
+                })?;
+
+                Ok(#value)
+            }
+        }
+        Data::Enum(data) => {
+            let mut attempts = Vec::new();
+            let mut expected = Vec::new();
+
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let lit = pattern_attr(&variant.attrs).unwrap_or_else(|| {
+                    Err(syn::Error::new_spanned(
+                        ident,
+                        "Every variant needs a #[pattern = \"...\"] attribute",
+                    ))
+                })?;
+                let pattern = parsed_pattern(&lit)?;
+                let literals = &pattern.literals;
+                let value =
+                    construct(quote!(Self::#ident), &variant.fields, &pattern, &lit, false)?;
+                let binding = if pattern.placeholders.is_empty() {
+                    quote!(_)
+                } else {
+                    quote!(fields)
+                };
+
+                attempts.push(quote! {
+                    if let Some(#binding) = span.split_pattern(&[#(#literals),*]) {
+                        return Ok(#value);
+                    }
+                });
+                expected.push(format!("'{}'", lit.value()));
+            }
+
+            let expected = expected.join(", ");
+
+            quote! {
+                #(#attempts)*
+
+                Err(span.error(::anyhow::anyhow!("Expected one of {}", #expected)))
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "PuzzleParse doesn't support unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics crate::parse::FromSpan for #name #ty_generics #where_clause {
+            fn from_span(span: crate::parse::Span<'_>) -> ::anyhow::Result<Self> {
+                #body
+            }
+        }
+
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::anyhow::Error;
+
+            fn from_str(s: &str) -> ::anyhow::Result<Self> {
+                <Self as crate::parse::FromSpan>::from_span(crate::parse::Span::new(s))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn placeholder(field: &str, path: &[&str]) -> Placeholder {
+        Placeholder {
+            field: String::from(field),
+            path: path.iter().map(|s| String::from(*s)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(
+            parse_pattern("{x1},{y1} -> {x2},{y2}"),
+            Ok(Pattern {
+                literals: vec!["", ",", " -> ", ",", ""]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                placeholders: vec![
+                    placeholder("x1", &[]),
+                    placeholder("y1", &[]),
+                    placeholder("x2", &[]),
+                    placeholder("y2", &[]),
+                ],
+            })
+        );
+        assert_eq!(
+            parse_pattern("{{{start.x}}}"),
+            Ok(Pattern {
+                literals: vec![String::from("{"), String::from("}")],
+                placeholders: vec![placeholder("start", &["x"])],
+            })
+        );
+        assert_eq!(
+            parse_pattern("none"),
+            Ok(Pattern {
+                literals: vec![String::from("none")],
+                placeholders: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_pattern_errors() {
+        assert!(parse_pattern("{a}{b}").is_err());
+        assert!(parse_pattern("{}").is_err());
+        assert!(parse_pattern("{a.}").is_err());
+        assert!(parse_pattern("a}").is_err());
+        assert!(parse_pattern("{a").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Integer(i64),
+    Unsigned(u64),
+    String(String),
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            Self::Integer(n) => Some(*n as i128),
+            Self::Unsigned(n) => Some(*n as i128),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{}", n),
+            Self::Unsigned(n) => write!(f, "{}", n),
+            Self::String(s) => write!(f, "{}", s),
+            Self::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.as_i128(), other.as_i128()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Self::Integer(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Self::Unsigned(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::Unsigned(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Self::Grid(rows)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-5i64).to_string(), "-5");
+        assert_eq!(Answer::from(5u64).to_string(), "5");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(
+            Answer::from(vec![String::from("#.#"), String::from(".#.")]).to_string(),
+            "#.#\n.#."
+        );
+    }
+
+    #[test]
+    fn test_answer_eq() {
+        assert_eq!(Answer::from(5i64), Answer::from(5u64));
+        assert_eq!(Answer::from(5usize), Answer::from("5"));
+        assert_ne!(Answer::from(-1i64), Answer::from(u64::MAX));
+        assert_eq!(
+            Answer::from(vec![String::from("#."), String::from(".#")]),
+            Answer::from("#.\n.#")
+        );
+    }
+
+    #[test]
+    fn test_answer_serialize() -> anyhow::Result<()> {
+        assert_eq!(serde_json::to_string(&Answer::from(-5i64))?, "-5");
+        assert_eq!(serde_json::to_string(&Answer::from(5u64))?, "5");
+        assert_eq!(serde_json::to_string(&Answer::from("abc"))?, r#""abc""#);
+        assert_eq!(
+            serde_json::to_string(&Answer::from(vec![String::from("#.")]))?,
+            r##"["#."]"##
+        );
+        Ok(())
+    }
+}
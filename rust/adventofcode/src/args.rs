@@ -1,6 +1,13 @@
+use anyhow::{bail, Result};
 use clap::Parser;
 use tracing_subscriber::EnvFilter;
 
+use crate::bench::Bench;
+use crate::output::Format;
+use crate::runner::RunAll;
+use crate::scaffold::NewDay;
+use crate::source::InputSource;
+use crate::verify::Verify;
 use crate::Solutions;
 
 #[derive(Debug, Parser)]
@@ -8,12 +15,58 @@ pub struct Args {
     #[clap(long, default_value = "warn", global = true)]
     pub logging_filter: String,
 
+    /// Read the puzzle input from this file, or from stdin if '-'
+    #[clap(long, global = true)]
+    pub input: Option<InputSource>,
+
+    /// Run against the puzzle's example input from inputs/examples
+    #[clap(long, global = true, conflicts_with = "input")]
+    pub example: bool,
+
+    /// Output format for answers and bench reports: plain, json or table
+    #[clap(long, global = true)]
+    pub format: Option<Format>,
+
     #[clap(subcommand)]
-    pub command: Solutions,
+    pub command: Action,
+}
+
+#[derive(Debug, Parser)]
+pub enum Action {
+    /// Run every registered day and part
+    All(RunAll),
+    /// Time a single day and part over many iterations
+    Bench(Bench),
+    /// Check solutions against the recorded answers
+    Verify(Verify),
+    /// Create a new day's module and input file from solution.rs.skel
+    NewDay(NewDay),
+    #[clap(flatten)]
+    Solution(Solutions),
 }
 
 impl Args {
     pub fn env_filter(&self) -> EnvFilter {
         self.logging_filter.as_str().into()
     }
+
+    pub fn input_source(&self) -> InputSource {
+        if self.example {
+            InputSource::Example
+        } else {
+            self.input.clone().unwrap_or_default()
+        }
+    }
+
+    /// The input source for commands that run more than one day
+    pub fn shared_input_source(&self, command: &str) -> Result<InputSource> {
+        if self.input.is_some() {
+            bail!(
+                "--input cannot be used with '{}', each day reads its own input",
+                command
+            );
+        }
+
+        Ok(self.input_source())
+    }
 }
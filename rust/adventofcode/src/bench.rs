@@ -0,0 +1,285 @@
+use std::time::Duration;
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+use serde::Serialize;
+
+use crate::command::Part;
+use crate::output::Format;
+use crate::source::InputSource;
+use crate::{Day, Solutions};
+
+// The counting allocator is installed for the whole binary, so it's behind a feature
+// rather than making every command pay for the atomic updates
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    struct CountingAllocator;
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    pub fn allocation_counts() -> Option<(u64, u64)> {
+        Some((
+            ALLOCATIONS.load(Ordering::Relaxed),
+            ALLOCATED_BYTES.load(Ordering::Relaxed),
+        ))
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+use counting::allocation_counts;
+
+#[cfg(not(feature = "count-allocations"))]
+fn allocation_counts() -> Option<(u64, u64)> {
+    None
+}
+
+#[derive(Debug, Parser)]
+pub struct Bench {
+    #[clap(short = 'n', long, default_value = "100")]
+    pub iterations: usize,
+
+    /// The day to run with its options and part, the same as running it directly,
+    /// e.g. 'day05 --algorithm intersect part2'
+    #[clap(subcommand)]
+    pub solution: Solutions,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Timings {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Timings {
+    fn new(mut timings: Vec<Duration>) -> Self {
+        timings.sort();
+
+        let iterations = timings.len();
+        let nanos = |d: Duration| d.as_nanos() as u64;
+        let percentile = |p: usize| timings[(iterations * p).div_ceil(100).max(1) - 1];
+        let total: Duration = timings.iter().sum();
+
+        Timings {
+            min_ns: nanos(timings[0]),
+            median_ns: nanos(percentile(50)),
+            mean_ns: nanos(total / iterations as u32),
+            p95_ns: nanos(percentile(95)),
+        }
+    }
+
+    fn print(&self, name: &str) {
+        let format = |ns: u64| format!("{:?}", Duration::from_nanos(ns));
+
+        println!(
+            "  {:<12} min {}, median {}, mean {}, p95 {}",
+            format!("{}:", name),
+            format(self.min_ns),
+            format(self.median_ns),
+            format(self.mean_ns),
+            format(self.p95_ns)
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchReport {
+    pub day: u32,
+    pub part: u32,
+    pub iterations: usize,
+    pub parse: Timings,
+    pub solve: Timings,
+    /// Per iteration, parsing included. Only counted with the `count-allocations` feature.
+    pub allocations: Option<u64>,
+    pub allocated_bytes: Option<u64>,
+}
+
+impl BenchReport {
+    fn new(
+        day: Day,
+        part: Part,
+        parse: Vec<Duration>,
+        solve: Vec<Duration>,
+        allocations: Option<(u64, u64)>,
+    ) -> Self {
+        let iterations = solve.len() as u64;
+
+        BenchReport {
+            day: day.number(),
+            part: part.number(),
+            iterations: solve.len(),
+            parse: Timings::new(parse),
+            solve: Timings::new(solve),
+            allocations: allocations.map(|(count, _)| count / iterations),
+            allocated_bytes: allocations.map(|(_, bytes)| bytes / iterations),
+        }
+    }
+
+    fn print(&self) {
+        println!("day{:02} part{}", self.day, self.part);
+        println!("  iterations:  {}", self.iterations);
+        self.parse.print("parse");
+        self.solve.print("solve");
+        match (self.allocations, self.allocated_bytes) {
+            (Some(allocations), Some(bytes)) => {
+                println!("  allocations: {} ({} bytes)", allocations, bytes)
+            }
+            _ => println!("  allocations: not counted, build with --features count-allocations"),
+        }
+    }
+
+    fn print_table(&self) {
+        let format = |ns: u64| format!("{:.2?}", Duration::from_nanos(ns));
+
+        println!(
+            "{:<6} {:<6} {:>10} {:>12} {:>12} {:>12} Allocations",
+            "Day", "Part", "Iterations", "Parse", "Solve", "Solve p95"
+        );
+        println!(
+            "{:<6} {:<6} {:>10} {:>12} {:>12} {:>12} {}",
+            format!("day{:02}", self.day),
+            format!("part{}", self.part),
+            self.iterations,
+            format(self.parse.median_ns),
+            format(self.solve.median_ns),
+            format(self.solve.p95_ns),
+            self.allocations
+                .map_or_else(|| String::from("-"), |n| n.to_string())
+        );
+    }
+}
+
+#[tracing::instrument(level = "debug", skip(input))]
+fn bench(solution: &Solutions, iterations: usize, input: &str) -> Result<BenchReport> {
+    ensure!(iterations > 0, "At least one iteration is required");
+
+    let mut parse = Vec::with_capacity(iterations);
+    let mut solve = Vec::with_capacity(iterations);
+    let start = allocation_counts();
+
+    // Each iteration parses again so parse times are measured too, but they're kept apart
+    for _ in 0..iterations {
+        let run = solution.run(input)?;
+        parse.push(run.parse_elapsed);
+
+        for result in run.parts {
+            result.answer?;
+            solve.push(result.elapsed);
+        }
+    }
+
+    let allocations = start.zip(allocation_counts()).map(
+        |((start_count, start_bytes), (end_count, end_bytes))| {
+            (end_count - start_count, end_bytes - start_bytes)
+        },
+    );
+
+    Ok(BenchReport::new(
+        solution.day(),
+        solution.part(),
+        parse,
+        solve,
+        allocations,
+    ))
+}
+
+pub fn run_bench(args: &Bench, source: &InputSource, format: Option<Format>) -> Result<()> {
+    let input = source.read(args.solution.day())?;
+    let report = bench(&args.solution, args.iterations, &input)?;
+
+    match format.unwrap_or(Format::Plain) {
+        Format::Plain => report.print(),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        Format::Table => report.print_table(),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_report_statistics() {
+        let timings = (1..=100).rev().map(Duration::from_nanos).collect();
+        let parse = (1..=100).map(|n| Duration::from_nanos(n * 10)).collect();
+        let report = BenchReport::new(Day::day01, Part::Two, parse, timings, Some((250, 1000)));
+
+        assert_eq!(
+            report,
+            BenchReport {
+                day: 1,
+                part: 2,
+                iterations: 100,
+                parse: Timings {
+                    min_ns: 10,
+                    median_ns: 500,
+                    mean_ns: 505,
+                    p95_ns: 950,
+                },
+                solve: Timings {
+                    min_ns: 1,
+                    median_ns: 50,
+                    mean_ns: 50,
+                    p95_ns: 95,
+                },
+                allocations: Some(2),
+                allocated_bytes: Some(10),
+            }
+        );
+    }
+
+    #[test]
+    fn test_bench() -> Result<()> {
+        let day06 = Solutions::day06 {
+            options: Default::default(),
+            part: Part::One,
+        };
+        let report = bench(&day06, 3, "3,4,3,1,2")?;
+
+        assert_eq!(report.iterations, 3);
+        assert!(report.solve.min_ns <= report.solve.median_ns);
+        assert!(report.solve.median_ns <= report.solve.p95_ns);
+        assert!(report.parse.min_ns <= report.parse.median_ns);
+        assert_eq!(
+            report.allocations.is_some(),
+            cfg!(feature = "count-allocations")
+        );
+        assert!(report.allocations.is_none_or(|n| n > 0));
+
+        let day05 =
+            Solutions::try_parse_from(["bench", "day05", "--algorithm", "intersect", "part2"])?;
+        let report = bench(&day05, 2, "0,9 -> 5,9\n0,9 -> 2,9\n")?;
+
+        assert_eq!((report.day, report.part, report.iterations), (5, 2, 2));
+
+        Ok(())
+    }
+}
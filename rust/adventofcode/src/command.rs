@@ -1,5 +1,42 @@
-use anyhow::Result;
+use std::fmt;
+use std::str::FromStr;
 
-pub trait Command {
-    fn execute(&self) -> Result<String>;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Parser)]
+pub enum Part {
+    #[clap(name = "part1")]
+    One,
+    #[clap(name = "part2")]
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("part{}", self.number()))
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim_start_matches("part") {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            _ => Err(anyhow!("Unrecognized part: '{}'", s)),
+        }
+    }
 }
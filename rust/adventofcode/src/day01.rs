@@ -1,20 +1,28 @@
 use anyhow::Result;
-use clap::Parser;
 
-use crate::{input, Command};
+use crate::parse;
+use crate::{Answer, NoOptions, Solution};
 
-#[derive(Debug, Parser)]
-pub enum Args {
-    Part1,
-    Part2,
-}
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<i64>;
+    type Options = NoOptions;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::lines(input)
+    }
+
+    fn part_one(measurements: &Self::Input) -> Result<Answer> {
+        let deltas = find_deltas(measurements);
+
+        Ok(deltas.iter().filter(|x| **x > 0).count().into())
+    }
+
+    fn part_two(measurements: &Self::Input) -> Result<Answer> {
+        let deltas = find_sliding_deltas(measurements);
 
-impl Command for Args {
-    fn execute(&self) -> Result<String> {
-        match self {
-            Self::Part1 => part_one(),
-            Self::Part2 => part_two(),
-        }
+        Ok(deltas.iter().filter(|x| **x > 0).count().into())
     }
 }
 
@@ -40,37 +48,16 @@ fn find_sliding_deltas(measurements: &[i64]) -> Vec<i64> {
         .collect::<Vec<_>>()
 }
 
-fn part_one() -> Result<String> {
-    let measurements = input(crate::Day::day01)
-        .lines()
-        .map(|s| s.trim().parse::<i64>())
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let deltas = find_deltas(&measurements);
-
-    Ok(deltas.iter().filter(|x| **x > 0).count().to_string())
-}
-
-fn part_two() -> Result<String> {
-    let measurements = input(crate::Day::day01)
-        .lines()
-        .map(|s| s.trim().parse::<i64>())
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let deltas = find_sliding_deltas(&measurements);
-
-    Ok(deltas.iter().filter(|x| **x > 0).count().to_string())
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{example, Day};
     use tracing_test::traced_test;
 
     #[traced_test]
     #[test]
     fn test_part_one() -> Result<()> {
-        let report = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let report = Solver::parse(example(Day::day01).unwrap())?;
         let deltas = find_deltas(&report);
 
         assert_eq!(deltas.len(), 9);
@@ -87,7 +74,7 @@ mod test {
     #[traced_test]
     #[test]
     fn test_part_two() -> Result<()> {
-        let report = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let report = Solver::parse(example(Day::day01).unwrap())?;
         let deltas = find_sliding_deltas(&report);
 
         assert_eq!(deltas.len(), 7);
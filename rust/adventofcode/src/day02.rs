@@ -1,120 +1,243 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::Parser;
+use serde::Serialize;
 
-use crate::{input, Command};
+use crate::parse;
+use crate::{Answer, Part, Point, PuzzleParse, Solution};
 
-#[derive(Debug, Parser)]
-pub enum Args {
-    Part1,
-    Part2,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    Plain,
+    Csv,
+    Json,
 }
 
-impl Command for Args {
-    fn execute(&self) -> Result<String> {
-        match self {
-            Self::Part1 => part_one(),
-            Self::Part2 => part_two(),
+impl FromStr for TraceFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow!("Unrecognized trace format: '{}'", s)),
         }
     }
 }
 
-enum SubCommand {
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    /// Print the submarine's position after every command: plain, csv or json
+    #[clap(long)]
+    pub trace: Option<TraceFormat>,
+
+    /// Write the trace to this file instead of stderr
+    #[clap(long, requires = "trace")]
+    pub trace_file: Option<PathBuf>,
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<SubCommand>;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::lines(input)
+    }
+
+    fn part_one(commands: &Self::Input) -> Result<Answer> {
+        Self::solve(commands, Part::One, &Options::default())
+    }
+
+    fn part_two(commands: &Self::Input) -> Result<Answer> {
+        Self::solve(commands, Part::Two, &Options::default())
+    }
+
+    fn solve(commands: &Self::Input, part: Part, options: &Options) -> Result<Answer> {
+        let steering = match part {
+            Part::One => Steering::Direct,
+            Part::Two => Steering::Aim,
+        };
+
+        let submarine = match options.trace {
+            Some(format) => {
+                let states = Submarine::new(steering).trace(commands);
+
+                match &options.trace_file {
+                    Some(path) => File::create(path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|mut file| write_trace(&mut file, format, commands, &states))
+                        .with_context(|| {
+                            format!("Unable to write trace to '{}'", path.display())
+                        })?,
+                    None => write_trace(&mut io::stderr().lock(), format, commands, &states)?,
+                }
+
+                states.last().copied().unwrap_or_else(|| Submarine::new(steering))
+            }
+            None => Submarine::new(steering).run(commands),
+        };
+
+        Ok(submarine.product().into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PuzzleParse)]
+pub enum SubCommand {
+    #[pattern = "forward {0}"]
     Forward(i64),
+    #[pattern = "up {0}"]
     Up(i64),
+    #[pattern = "down {0}"]
     Down(i64),
 }
 
-impl FromStr for SubCommand {
-    type Err = anyhow::Error;
-
-    fn from_str(command: &str) -> Result<Self, <Self as FromStr>::Err> {
-        let mut command_parts = command.split(' ');
-
-        match (command_parts.next(), command_parts.next()) {
-            (Some("forward"), Some(n)) => Ok(SubCommand::Forward(n.parse()?)),
-            (Some("up"), Some(n)) => Ok(SubCommand::Up(n.parse()?)),
-            (Some("down"), Some(n)) => Ok(SubCommand::Down(n.parse()?)),
-            _ => Err(anyhow!("Unrecognized line: '{:?}'", command)),
+impl fmt::Display for SubCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubCommand::Forward(n) => write!(f, "forward {}", n),
+            SubCommand::Up(n) => write!(f, "up {}", n),
+            SubCommand::Down(n) => write!(f, "down {}", n),
         }
     }
 }
 
-fn sub_part_one<I, S>(commands: I) -> i64
-where
-    I: IntoIterator<Item = S>,
-    S: AsRef<str>,
-{
-    let (x, y) = commands
-        .into_iter()
-        .fold((0, 0), |(mut x, mut y), command| {
-            let command = command.as_ref().parse::<SubCommand>().unwrap();
-
-            match command {
-                SubCommand::Forward(n) => x += n,
-                SubCommand::Up(n) => y -= n,
-                SubCommand::Down(n) => y += n,
-            };
-
-            (x, y)
-        });
+/// What up and down do: part one moves the submarine, part two turns it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Steering {
+    #[default]
+    Direct,
+    Aim,
+}
 
-    x * y
+/// `position.y` is the depth, increasing downwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Submarine {
+    pub position: Point<i64>,
+    pub aim: i64,
+    pub steering: Steering,
 }
 
-fn sub_part_two<I, S>(commands: I) -> i64
-where
-    I: IntoIterator<Item = S>,
-    S: AsRef<str>,
-{
-    let (_, x, y) = commands
-        .into_iter()
-        .fold((0, 0, 0), |(mut aim, mut x, mut y), command| {
-            let command = command.as_ref().parse::<SubCommand>().unwrap();
-
-            match command {
-                SubCommand::Forward(n) => {
-                    x += n;
-                    y += aim * n;
-                }
-                SubCommand::Up(n) => aim -= n,
-                SubCommand::Down(n) => aim += n,
-            };
+impl Submarine {
+    pub fn new(steering: Steering) -> Self {
+        Self {
+            steering,
+            ..Default::default()
+        }
+    }
 
-            (aim, x, y)
-        });
+    pub fn step(&mut self, command: SubCommand) {
+        match (self.steering, command) {
+            (Steering::Direct, SubCommand::Forward(n)) => self.position.x += n,
+            (Steering::Direct, SubCommand::Up(n)) => self.position.y -= n,
+            (Steering::Direct, SubCommand::Down(n)) => self.position.y += n,
+            (Steering::Aim, SubCommand::Forward(n)) => {
+                self.position += Point::new(n, self.aim * n);
+            }
+            (Steering::Aim, SubCommand::Up(n)) => self.aim -= n,
+            (Steering::Aim, SubCommand::Down(n)) => self.aim += n,
+        }
+    }
+
+    pub fn run(mut self, commands: &[SubCommand]) -> Self {
+        for command in commands {
+            self.step(*command);
+        }
+
+        self
+    }
+
+    /// The submarine after each of the commands
+    pub fn trace(mut self, commands: &[SubCommand]) -> Vec<Self> {
+        commands
+            .iter()
+            .map(|command| {
+                self.step(*command);
+                self
+            })
+            .collect()
+    }
 
-    x * y
+    pub fn product(&self) -> i64 {
+        self.position.x * self.position.y
+    }
 }
 
-fn part_one() -> Result<String> {
-    Ok(sub_part_one(input(crate::Day::day02).lines()).to_string())
+#[derive(Debug, Serialize)]
+struct TraceRecord {
+    step: usize,
+    command: String,
+    x: i64,
+    depth: i64,
+    aim: i64,
 }
 
-fn part_two() -> Result<String> {
-    Ok(sub_part_two(input(crate::Day::day02).lines()).to_string())
+fn write_trace(
+    out: &mut dyn Write,
+    format: TraceFormat,
+    commands: &[SubCommand],
+    states: &[Submarine],
+) -> Result<()> {
+    let records = commands
+        .iter()
+        .zip(states)
+        .enumerate()
+        .map(|(i, (command, state))| TraceRecord {
+            step: i + 1,
+            command: command.to_string(),
+            x: state.position.x,
+            depth: state.position.y,
+            aim: state.aim,
+        });
+
+    match format {
+        TraceFormat::Plain => {
+            for r in records {
+                writeln!(
+                    out,
+                    "{:>5} {:<12} x={} depth={} aim={}",
+                    r.step, r.command, r.x, r.depth, r.aim
+                )?;
+            }
+        }
+        TraceFormat::Csv => {
+            writeln!(out, "step,command,x,depth,aim")?;
+
+            for r in records {
+                writeln!(out, "{},{},{},{},{}", r.step, r.command, r.x, r.depth, r.aim)?;
+            }
+        }
+        TraceFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, &records.collect::<Vec<_>>())?;
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{example, input, Day};
     use tracing_test::traced_test;
 
-    const TEST_INPUT: &'static [&'static str] = &[
-        "forward 5",
-        "down 5",
-        "forward 8",
-        "up 3",
-        "down 8",
-        "forward 2",
-    ];
+    fn product(steering: Steering, commands: &[SubCommand]) -> i64 {
+        Submarine::new(steering).run(commands).product()
+    }
 
     #[traced_test]
     #[test]
     fn test_part_one() -> Result<()> {
-        assert_eq!(sub_part_one(TEST_INPUT), 150);
-        assert_eq!(sub_part_one(input(crate::Day::day02).lines()), 2120749);
+        assert_eq!(product(Steering::Direct, &Solver::parse(example(Day::day02).unwrap())?), 150);
+        assert_eq!(product(Steering::Direct, &Solver::parse(input(Day::day02))?), 2120749);
 
         Ok(())
     }
@@ -122,8 +245,104 @@ mod test {
     #[traced_test]
     #[test]
     fn test_part_two() -> Result<()> {
-        assert_eq!(sub_part_two(TEST_INPUT), 900);
-        assert_eq!(sub_part_two(input(crate::Day::day02).lines()), 2138382217);
+        assert_eq!(product(Steering::Aim, &Solver::parse(example(Day::day02).unwrap())?), 900);
+        assert_eq!(product(Steering::Aim, &Solver::parse(input(Day::day02))?), 2138382217);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        assert_eq!(
+            Solver::parse("forward 5\n  down 3\nup 2\n")?,
+            vec![
+                SubCommand::Forward(5),
+                SubCommand::Down(3),
+                SubCommand::Up(2)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let message = |input| format!("{:#}", Solver::parse(input).unwrap_err());
+
+        assert_eq!(
+            message("forward 5\nsideways 3"),
+            "Invalid value 'sideways 3' at line 2, column 1: Expected one of 'forward {0}', 'up {0}', 'down {0}'"
+        );
+        assert_eq!(
+            message("down x"),
+            "Invalid value 'x' at line 1, column 6: invalid digit found in string"
+        );
+        assert_eq!(
+            message("up 1\nup\n"),
+            "Invalid value 'up' at line 2, column 1: Expected one of 'forward {0}', 'up {0}', 'down {0}'"
+        );
+        assert_eq!(
+            message("forward 1\n\nforward 2"),
+            "Invalid value '' at line 2, column 1: Expected one of 'forward {0}', 'up {0}', 'down {0}'"
+        );
+    }
+
+    #[test]
+    fn test_submarine() -> Result<()> {
+        let commands = Solver::parse(example(Day::day02).unwrap())?;
+
+        assert_eq!(
+            Submarine::new(Steering::Direct)
+                .trace(&commands)
+                .iter()
+                .map(|s| s.position)
+                .collect::<Vec<_>>(),
+            vec![
+                Point::new(5, 0),
+                Point::new(5, 5),
+                Point::new(13, 5),
+                Point::new(13, 2),
+                Point::new(13, 10),
+                Point::new(15, 10)
+            ]
+        );
+
+        let states = Submarine::new(Steering::Aim).trace(&commands);
+
+        assert_eq!(
+            states.iter().map(|s| s.aim).collect::<Vec<_>>(),
+            vec![0, 5, 5, 2, 10, 10]
+        );
+        assert_eq!(states.last(), Some(&Submarine::new(Steering::Aim).run(&commands)));
+        assert_eq!(states[5].position, Point::new(15, 60));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_trace() -> Result<()> {
+        let commands = Solver::parse("forward 5\ndown 5\nforward 8")?;
+        let states = Submarine::new(Steering::Aim).trace(&commands);
+        let trace = |format| -> Result<String> {
+            let mut out = Vec::new();
+            write_trace(&mut out, format, &commands, &states)?;
+            Ok(String::from_utf8(out)?)
+        };
+
+        assert_eq!(
+            trace(TraceFormat::Csv)?,
+            "step,command,x,depth,aim\n1,forward 5,5,0,0\n2,down 5,5,0,5\n3,forward 8,13,40,5\n"
+        );
+        assert_eq!(
+            trace(TraceFormat::Plain)?.lines().last(),
+            Some("    3 forward 8    x=13 depth=40 aim=5")
+        );
+
+        let json = serde_json::from_str::<serde_json::Value>(&trace(TraceFormat::Json)?)?;
+        assert_eq!(
+            json[2],
+            serde_json::json!({"step": 3, "command": "forward 8", "x": 13, "depth": 40, "aim": 5})
+        );
+
         Ok(())
     }
 }
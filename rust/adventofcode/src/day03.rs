@@ -1,22 +1,49 @@
-use std::num::ParseIntError;
+use anyhow::{anyhow, Result};
 
-use anyhow::Result;
-use clap::Parser;
+use crate::parse::Span;
+use crate::{Answer, NoOptions, Solution};
 
-use crate::{input, Command};
+pub struct Solver;
 
-#[derive(Debug, Parser)]
-pub enum Args {
-    Part1,
-    Part2,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+    bits: usize,
+    items: Vec<u64>,
 }
 
-impl Command for Args {
-    fn execute(&self) -> Result<String> {
-        match self {
-            Self::Part1 => part_one(),
-            Self::Part2 => part_two(),
-        }
+impl Solution for Solver {
+    type Input = Diagnostics;
+    type Options = NoOptions;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let bits = input
+            .lines()
+            .next()
+            .map(str::len)
+            .ok_or_else(|| anyhow!("No diagnostics"))?;
+
+        Ok(Diagnostics {
+            bits,
+            items: Span::new(input)
+                .lines()
+                .map(|l| l.parse_with(|s| u64::from_str_radix(s, 2)))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    #[tracing::instrument(level = "debug", skip(diagnostics))]
+    fn part_one(diagnostics: &Self::Input) -> Result<Answer> {
+        let Diagnostics { bits, items } = diagnostics;
+        let mcb = find_most_common_bits(items, *bits);
+
+        Ok((mcb * sized_inverse(*bits, !mcb)).into())
+    }
+
+    #[tracing::instrument(level = "debug", skip(diagnostics))]
+    fn part_two(diagnostics: &Self::Input) -> Result<Answer> {
+        let Diagnostics { bits, items } = diagnostics;
+
+        Ok((find_rating(items, *bits, true) * find_rating(items, *bits, false)).into())
     }
 }
 
@@ -74,10 +101,7 @@ where
             }
         };
 
-        items = items
-            .into_iter()
-            .filter(|n| *n & (1 << bit(bits, idx)) == needle & (1 << bit(bits, idx)))
-            .collect();
+        items.retain(|n| *n & (1 << bit(bits, idx)) == needle & (1 << bit(bits, idx)));
 
         if items.len() == 1 {
             return items[0];
@@ -87,51 +111,29 @@ where
     panic!("Should never reach here");
 }
 
-#[tracing::instrument(level = "debug")]
-fn part_one() -> Result<String> {
-    let mcb = find_most_common_bits(
-        &input(crate::Day::day03)
-            .lines()
-            .map(|l| u64::from_str_radix(l, 2))
-            .collect::<Result<Vec<_>, ParseIntError>>()?,
-        12,
-    );
-
-    Ok((mcb * sized_inverse(12, !mcb)).to_string())
-}
-
-#[tracing::instrument(level = "debug")]
-fn part_two() -> Result<String> {
-    let items = input(crate::Day::day03)
-        .lines()
-        .map(|l| u64::from_str_radix(l, 2))
-        .collect::<Result<Vec<_>, ParseIntError>>()?;
-
-    Ok((find_rating(&items, 12, true) * find_rating(&items, 12, false)).to_string())
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
-
-    const TEST_INPUT: [u64; 12] = [
-        0b00100, 0b11110, 0b10110, 0b10111, 0b10101, 0b01111, 0b00111, 0b11100, 0b10000, 0b11001,
-        0b00010, 0b01010,
-    ];
+    use crate::{example, Day};
 
     #[tracing_test::traced_test]
     #[test]
     fn test_part_one() -> Result<()> {
-        assert_eq!(find_most_common_bits(&TEST_INPUT, 5), 22);
-        assert_eq!(sized_inverse(5, !find_most_common_bits(&TEST_INPUT, 5)), 9);
+        let Diagnostics { bits, items } = Solver::parse(example(Day::day03).unwrap())?;
+
+        assert_eq!(bits, 5);
+        assert_eq!(find_most_common_bits(&items, 5), 22);
+        assert_eq!(sized_inverse(5, !find_most_common_bits(&items, 5)), 9);
         Ok(())
     }
 
     #[tracing_test::traced_test]
     #[test]
     fn test_part_two() -> Result<()> {
-        assert_eq!(find_rating(&TEST_INPUT, 5, true), 23);
-        assert_eq!(find_rating(&TEST_INPUT, 5, false), 10);
+        let Diagnostics { items, .. } = Solver::parse(example(Day::day03).unwrap())?;
+
+        assert_eq!(find_rating(&items, 5, true), 23);
+        assert_eq!(find_rating(&items, 5, false), 10);
         Ok(())
     }
 }
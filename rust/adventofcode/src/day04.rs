@@ -1,31 +1,44 @@
-use std::convert::TryFrom;
-use std::convert::TryInto;
 use std::str::FromStr;
 
-use anyhow::{anyhow, Context, Result};
-use clap::Parser;
-use once_cell::sync::Lazy;
+use anyhow::{anyhow, Result};
 
-use crate::{input, Command};
+use crate::parse::{self, Span};
+use crate::{Answer, Grid, NoOptions, Solution};
 
-#[derive(Debug, Parser)]
-pub enum Args {
-    Part1,
-    Part2,
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = BingoGame;
+    type Options = NoOptions;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part_one(game: &Self::Input) -> Result<Answer> {
+        if let BingoCardStatus::Solved { call, sum } = game.clone().find_winning_call() {
+            Ok((call * sum).into())
+        } else {
+            Err(anyhow!("No winning call"))
+        }
+    }
+
+    fn part_two(game: &Self::Input) -> Result<Answer> {
+        if let Some(BingoCardStatus::Solved { call, sum }) = game.clone().find_last_winner() {
+            Ok((call * sum).into())
+        } else {
+            Err(anyhow!("No winning call"))
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum BingoCellStatus {
     Marked,
+    #[default]
     Unmarked,
 }
 
-impl Default for BingoCellStatus {
-    fn default() -> Self {
-        BingoCellStatus::Unmarked
-    }
-}
-
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct BingoCell {
     value: u64,
@@ -51,28 +64,22 @@ impl FromStr for BingoCell {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(BingoCell {
-            value: s.trim().parse()?,
+            value: s.parse()?,
             ..Default::default()
         })
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum BingoCardStatus {
+    #[default]
     Unsolved,
     Solved { call: u64, sum: u64 },
 }
 
-impl Default for BingoCardStatus {
-    fn default() -> Self {
-        BingoCardStatus::Unsolved
-    }
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct BingoCard {
-    cells: Vec<Vec<BingoCell>>,
-    //[[BingoCell; 5]; 5],
+    cells: Grid<BingoCell>,
     status: BingoCardStatus,
 }
 
@@ -83,47 +90,20 @@ impl BingoCard {
 
     fn unmarked_sum(&self) -> u64 {
         self.cells
-            .iter()
-            .map::<u64, _>(|row| {
-                row.iter()
-                    .filter_map(|c| {
-                        if matches!(c.status(), BingoCellStatus::Unmarked) {
-                            Some(c.value())
-                        } else {
-                            None
-                        }
-                    })
-                    .sum()
-            })
+            .cells()
+            .filter(|c| matches!(c.status(), BingoCellStatus::Unmarked))
+            .map(|c| c.value())
             .sum()
     }
 
     fn mark_value(&mut self, value: u64) -> BingoCardStatus {
-        let mut marked: Option<(usize, usize)> = None;
-
-        for (y, row) in self.cells.iter_mut().enumerate() {
-            for (x, cell) in row.iter_mut().enumerate() {
-                if cell.value() == value {
-                    cell.mark();
+        if let Some(marked) = self.cells.position(|c| c.value() == value) {
+            self.cells[marked].mark();
 
-                    marked = Some((x, y));
-                    break;
-                }
-            }
+            let is_marked = |c: &BingoCell| c.status() == BingoCellStatus::Marked;
 
-            if marked.is_some() {
-                break;
-            }
-        }
-
-        if let Some((x, y)) = marked {
-            if self.cells[y]
-                .iter()
-                .all(|c| c.status() == BingoCellStatus::Marked)
-                || self
-                    .cells
-                    .iter()
-                    .all(|r| r[x].status() == BingoCellStatus::Marked)
+            if self.cells.row(marked.y as usize).iter().all(is_marked)
+                || self.cells.column(marked.x as usize).all(is_marked)
             {
                 self.status = BingoCardStatus::Solved {
                     call: value,
@@ -136,38 +116,25 @@ impl BingoCard {
     }
 }
 
-static ROW_REGEX: Lazy<regex::Regex> = Lazy::new(|| {
-    regex::Regex::new(r"^([ \d]{2}) ([ \d]{2}) ([ \d]{2}) ([ \d]{2}) ([ \d]{2})$").unwrap()
-});
+impl BingoCard {
+    fn from_span(span: Span) -> Result<Self> {
+        Ok(BingoCard {
+            cells: parse::whitespace_grid(span)?,
+            status: BingoCardStatus::Unsolved,
+        })
+    }
+}
 
 impl FromStr for BingoCard {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(BingoCard {
-            cells: s
-                .lines()
-                .take(5)
-                .filter_map(|l| {
-                    ROW_REGEX.captures(l).map(|captures| {
-                        captures
-                            .iter()
-                            .skip(1)
-                            .map(|c| {
-                                c.ok_or_else(|| anyhow!("Capture failed"))
-                                    .and_then(|c| c.as_str().parse::<BingoCell>())
-                            })
-                            .collect::<Result<Vec<_>>>()
-                    })
-                })
-                .collect::<Result<Vec<Vec<_>>>>()?,
-            status: BingoCardStatus::Unsolved,
-        })
+        Self::from_span(Span::new(s))
     }
 }
 
-#[derive(Debug, Default)]
-struct BingoGame {
+#[derive(Debug, Default, Clone)]
+pub struct BingoGame {
     calls: Vec<u64>,
     cards: Vec<BingoCard>,
 }
@@ -210,123 +177,33 @@ impl BingoGame {
     }
 }
 
-enum BingoGameParserState {
-    WaitingForCalls,
-    Calls(Vec<u64>),
-    Boards(Vec<u64>, Vec<BingoCard>),
-    Error(anyhow::Error),
-}
-
-impl TryFrom<BingoGameParserState> for BingoGame {
-    type Error = anyhow::Error;
-
-    fn try_from(state: BingoGameParserState) -> Result<Self, Self::Error> {
-        match state {
-            BingoGameParserState::Boards(calls, cards) => Ok(BingoGame { calls, cards }),
-            BingoGameParserState::Error(e) => Err(e.context("Unable to parse game")),
-            _ => Err(anyhow!("Unable to parse game: Unknown error")),
-        }
-    }
-}
-
 impl FromStr for BingoGame {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.split("\n\n")
-            .fold(
-                BingoGameParserState::WaitingForCalls,
-                |state, chunk| match state {
-                    BingoGameParserState::WaitingForCalls => {
-                        let calls = chunk
-                            .split(',')
-                            .map(|c| {
-                                c.parse()
-                                    .map_err(anyhow::Error::from)
-                                    .with_context(|| format!("Invalid call value: '{}'", c))
-                            })
-                            .collect::<Result<Vec<_>>>();
-
-                        match calls {
-                            Ok(calls) => BingoGameParserState::Calls(calls),
-                            Err(e) => BingoGameParserState::Error(e),
-                        }
-                    }
-                    BingoGameParserState::Calls(calls) => match chunk.parse::<BingoCard>() {
-                        Ok(card) => BingoGameParserState::Boards(calls, vec![card]),
-                        Err(e) => BingoGameParserState::Error(e),
-                    },
-                    BingoGameParserState::Boards(calls, mut cards) => {
-                        match chunk.parse::<BingoCard>() {
-                            Ok(card) => {
-                                cards.push(card);
-                                BingoGameParserState::Boards(calls, cards)
-                            }
-                            Err(e) => BingoGameParserState::Error(e),
-                        }
-                    }
-                    _ => state,
-                },
-            )
-            .try_into()
-    }
-}
+        let mut blocks = parse::blocks(s);
 
-impl Command for Args {
-    fn execute(&self) -> Result<String> {
-        match self {
-            Self::Part1 => part_one(),
-            Self::Part2 => part_two(),
-        }
-    }
-}
+        let calls = blocks
+            .next()
+            .ok_or_else(|| anyhow!("Unable to parse game: No calls"))
+            .and_then(|calls| parse::parse_separated(calls, ","))?;
 
-fn part_one() -> Result<String> {
-    if let BingoCardStatus::Solved { call, sum } = input(crate::Day::day04)
-        .parse::<BingoGame>()?
-        .find_winning_call()
-    {
-        Ok((call * sum).to_string())
-    } else {
-        Err(anyhow!("No winning call"))
-    }
-}
+        let cards = blocks
+            .map(BingoCard::from_span)
+            .collect::<Result<Vec<_>>>()?;
+
+        if cards.is_empty() {
+            return Err(anyhow!("Unable to parse game: No cards"));
+        }
 
-fn part_two() -> Result<String> {
-    if let Some(BingoCardStatus::Solved { call, sum }) = input(crate::Day::day04)
-        .parse::<BingoGame>()?
-        .find_last_winner()
-    {
-        Ok((call * sum).to_string())
-    } else {
-        Err(anyhow!("No winning call"))
+        Ok(BingoGame { calls, cards })
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-
-    const TEST_INPUT: &'static str =
-        "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-22 13 17 11  0
- 8  2 23  4 24
-21  9 14 16  7
- 6 10  3 18  5
- 1 12 20 15 19
-
- 3 15  0  2 22
- 9 18 13 17  5
-19  8  7 25 23
-20 11 10 24  4
-14 21 16 12  6
-
-14 21 17 24  4
-10 16 15  9 19
-18  8 23 26 20
-22 11 13  6  5
- 2  0 12  3  7";
+    use crate::{example, Day};
 
     #[tracing_test::traced_test]
     #[test]
@@ -362,7 +239,7 @@ mod test {
     #[tracing_test::traced_test]
     #[test]
     fn test_part_one() -> Result<()> {
-        let mut game = TEST_INPUT.parse::<BingoGame>()?;
+        let mut game = Solver::parse(example(Day::day04).unwrap())?;
 
         assert_eq!(
             game.calls,
@@ -383,7 +260,7 @@ mod test {
     #[tracing_test::traced_test]
     #[test]
     fn test_part_two() -> Result<()> {
-        let mut game = TEST_INPUT.parse::<BingoGame>()?;
+        let mut game = Solver::parse(example(Day::day04).unwrap())?;
         assert_eq!(
             game.find_last_winner(),
             Some(BingoCardStatus::Solved { call: 13, sum: 148 })
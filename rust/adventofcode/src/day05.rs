@@ -1,31 +1,88 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
-use itertools::Itertools;
-use once_cell::sync::Lazy;
 
-use crate::{input, Command};
-use crate::{Dimension, DimensionedValue, Point};
+use crate::parse::{FromSpan, Span};
+use crate::{Answer, Part, PuzzleParse, Solution};
+use crate::{
+    Dimension, DimensionedValue, Dimensioned, InclusiveRange, Intersection, Orientation, Point,
+    Segment, SparseGrid,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    #[default]
+    Rasterise,
+    Intersect,
+}
+
+impl FromStr for Algorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rasterise" => Ok(Self::Rasterise),
+            "intersect" => Ok(Self::Intersect),
+            _ => Err(anyhow!("Unrecognized algorithm: '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    /// How to count overlaps: 'rasterise' every point, or 'intersect' the segments pairwise
+    #[clap(long, default_value = "rasterise")]
+    pub algorithm: Algorithm,
 
-#[derive(Debug, Parser)]
-pub enum Args {
-    Part1,
-    Part2,
+    /// Print how many segments cover each point to stderr
+    #[clap(long)]
+    pub display: bool,
 }
 
-impl Command for Args {
-    fn execute(&self) -> Result<String> {
-        match self {
-            Self::Part1 => part_one(),
-            Self::Part2 => part_two(),
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<LineSegment>;
+    type Options = Options;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Span::new(input)
+            .lines()
+            .map(LineSegment::from_span)
+            .collect::<Result<Vec<_>>>()
+    }
+
+    fn part_one(segments: &Self::Input) -> Result<Answer> {
+        Self::solve(segments, Part::One, &Options::default())
+    }
+
+    fn part_two(segments: &Self::Input) -> Result<Answer> {
+        Self::solve(segments, Part::Two, &Options::default())
+    }
+
+    fn solve(segments: &Self::Input, part: Part, options: &Options) -> Result<Answer> {
+        let include_diagonal = part == Part::Two;
+
+        if options.display {
+            display_points(segments.iter().copied(), include_diagonal);
         }
+
+        let overlaps = match options.algorithm {
+            Algorithm::Rasterise => {
+                map_intersections(segments.iter().copied(), include_diagonal).len()
+            }
+            Algorithm::Intersect => count_overlaps(segments, include_diagonal),
+        };
+
+        Ok(overlaps.into())
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
-struct LineSegment {
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, PuzzleParse)]
+#[pattern = "{start.x},{start.y} -> {end.x},{end.y}"]
+pub struct LineSegment {
     start: Point<i64>,
     end: Point<i64>,
 }
@@ -44,56 +101,9 @@ fn gen_points(start: Point<i64>, end: Point<i64>, dimension: Dimension) -> Vec<P
     points
 }
 
-struct RangeCanBeNegativeInclusive {
-    start: i64,
-    end: i64,
-    current: i64,
-    step: i64,
-}
-
-impl RangeCanBeNegativeInclusive {
-    fn new(start: i64, end: i64) -> Self {
-        if start < end {
-            Self {
-                start,
-                end,
-                current: start - 1,
-                step: 1,
-            }
-        } else {
-            Self {
-                start,
-                end,
-                current: start + 1,
-                step: -1,
-            }
-        }
-    }
-}
-
-impl Iterator for RangeCanBeNegativeInclusive {
-    type Item = i64;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current == self.end {
-            None
-        } else {
-            self.current += self.step;
-            tracing::debug!(
-                step = self.step,
-                start = self.start,
-                end = self.end,
-                current = self.current,
-                "step"
-            );
-            Some(self.current)
-        }
-    }
-}
-
 fn gen_points_diagonal(start: Point<i64>, end: Point<i64>) -> Vec<Point<i64>> {
-    let points = RangeCanBeNegativeInclusive::new(start.x, end.x)
-        .zip(RangeCanBeNegativeInclusive::new(start.y, end.y))
+    let points = InclusiveRange::new(start.x, end.x)
+        .zip(InclusiveRange::new(start.y, end.y))
         .map(|(x, y)| Point::new(x, y))
         .collect::<Vec<_>>();
 
@@ -102,162 +112,183 @@ fn gen_points_diagonal(start: Point<i64>, end: Point<i64>) -> Vec<Point<i64>> {
         end=?end,
         points=?points,
         d=?end-start,
-        x_range=?RangeCanBeNegativeInclusive::new(start.x, end.x).collect::<Vec<_>>(),
-        y_range=?RangeCanBeNegativeInclusive::new(start.y, end.y).collect::<Vec<_>>(),
+        x_range=?InclusiveRange::new(start.x, end.x).collect::<Vec<_>>(),
+        y_range=?InclusiveRange::new(start.y, end.y).collect::<Vec<_>>(),
     );
 
     points
 }
 
 impl LineSegment {
+    #[allow(dead_code)]
     fn new(start: Point<i64>, end: Point<i64>) -> Self {
         LineSegment { start, end }
     }
 
     fn points(&self, include_diagonal: bool) -> Vec<Point<i64>> {
-        match (self.end.x - self.start.x, self.end.y - self.start.y) {
-            (_, 0) => gen_points(self.start, self.end, Dimension::X),
-            (0, _) => gen_points(self.start, self.end, Dimension::Y),
-            _ => {
-                if !include_diagonal {
-                    tracing::debug!(start=?self.start, end=?self.end, dx=?self.end.x - self.start.x, dy=?self.end.y-self.start.y, "Diagonal segment");
-                    Vec::new()
-                } else {
-                    gen_points_diagonal(self.start, self.end)
-                }
+        let segment = Segment::from(*self);
+
+        match segment.orientation() {
+            Orientation::Point | Orientation::Horizontal => {
+                gen_points(self.start, self.end, Dimension::X)
+            }
+            Orientation::Vertical => gen_points(self.start, self.end, Dimension::Y),
+            Orientation::Diagonal if include_diagonal => gen_points_diagonal(self.start, self.end),
+            Orientation::Sloped if include_diagonal => segment.lattice_points().collect(),
+            orientation => {
+                tracing::debug!(start=?self.start, end=?self.end, orientation=?orientation, "Skipping segment");
+                Vec::new()
             }
         }
     }
 }
 
-static LINE_SEGMENT_REGEX: Lazy<regex::Regex> = Lazy::new(|| {
-    regex::Regex::new(r"^(?P<x1>\d+),(?P<y1>\d+) -> (?P<x2>\d+),(?P<y2>\d+)$").unwrap()
-});
-
-impl FromStr for LineSegment {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(captures) = LINE_SEGMENT_REGEX.captures(s) {
-            let segment = LineSegment::new(
-                Point::new(
-                    captures
-                        .name("x1")
-                        .ok_or_else(|| anyhow!("x1 missing"))?
-                        .as_str()
-                        .parse()?,
-                    captures
-                        .name("y1")
-                        .ok_or_else(|| anyhow!("y1 missing"))?
-                        .as_str()
-                        .parse()?,
-                ),
-                Point::new(
-                    captures
-                        .name("x2")
-                        .ok_or_else(|| anyhow!("x2 missing"))?
-                        .as_str()
-                        .parse()?,
-                    captures
-                        .name("y2")
-                        .ok_or_else(|| anyhow!("y2 missing"))?
-                        .as_str()
-                        .parse()?,
-                ),
-            );
-
-            Ok(segment)
-        } else {
-            Err(anyhow!("Invalid line segment: '{}'", s))
-        }
+impl From<LineSegment> for Segment<i64> {
+    fn from(segment: LineSegment) -> Self {
+        Segment::new(segment.start, segment.end)
     }
 }
 
+fn count_points<I>(segments: I, include_diagonal: bool) -> SparseGrid<usize>
+where
+    I: IntoIterator<Item = LineSegment>,
+{
+    segments
+        .into_iter()
+        .flat_map(|segment| segment.points(include_diagonal))
+        .collect()
+}
+
 fn map_intersections<I>(segments: I, include_diagonal: bool) -> Vec<Point<i64>>
 where
     I: IntoIterator<Item = LineSegment>,
 {
-    let mut map: HashMap<Point<i64>, i64> = HashMap::new();
+    count_points(segments, include_diagonal)
+        .iter()
+        .filter_map(|(point, count)| if *count > 1 { Some(point) } else { None })
+        .collect::<Vec<_>>()
+}
 
-    for segment in segments {
-        for point in segment.points(include_diagonal) {
-            *map.entry(point).or_default() += 1;
-        }
+/// The infinite line a segment lies on, the same for every collinear segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Line {
+    step: Point<i64>,
+    offset: i128,
+}
+
+impl Line {
+    fn of(segment: &Segment<i64>) -> Self {
+        let step = segment.lattice_step();
+        let offset = step.x as i128 * segment.start.y as i128 - step.y as i128 * segment.start.x as i128;
+
+        Line { step, offset }
     }
 
-    map.into_iter()
-        .filter_map(|(point, count)| if count > 1 { Some(point) } else { None })
-        .collect::<Vec<_>>()
-}
+    /// Where `point` is along the line, increasing in the direction of `step`
+    fn position(&self, point: Point<i64>) -> i128 {
+        self.step.x as i128 * point.x as i128 + self.step.y as i128 * point.y as i128
+    }
 
-fn part_one() -> Result<String> {
-    let segments = input(crate::Day::day05)
-        .lines()
-        .map(|s| s.parse::<LineSegment>())
-        .collect::<Result<Vec<_>>>()?;
+    /// Joins overlapping segments on this line, returning disjoint segments from low to
+    /// high position
+    fn merge(&self, mut segments: Vec<Segment<i64>>) -> Vec<Segment<i64>> {
+        for segment in segments.iter_mut() {
+            if self.position(segment.start) > self.position(segment.end) {
+                *segment = segment.reversed();
+            }
+        }
 
-    // display_points(segments, 1024, 1024);
+        segments.sort_by_key(|segment| self.position(segment.start));
 
-    let intersections = map_intersections(segments, false);
+        let mut merged: Vec<Segment<i64>> = Vec::new();
 
-    Ok(intersections.len().to_string())
+        for segment in segments {
+            match merged.last_mut() {
+                Some(last) if self.position(segment.start) <= self.position(last.end) => {
+                    if self.position(segment.end) > self.position(last.end) {
+                        last.end = segment.end;
+                    }
+                }
+                _ => merged.push(segment),
+            }
+        }
+
+        merged
+    }
 }
 
-fn part_two() -> Result<String> {
-    let segments = input(crate::Day::day05)
-        .lines()
-        .map(|s| s.parse::<LineSegment>())
-        .collect::<Result<Vec<_>>>()?;
+/// Counts the points covered by more than one segment by intersecting every pair of
+/// segments, so the work depends on the number of segments rather than their length.
+/// Sloped segments only cover the integer points exactly on them.
+fn count_overlaps(segments: &[LineSegment], include_diagonal: bool) -> usize {
+    let segments = segments
+        .iter()
+        .map(|segment| Segment::from(*segment))
+        .filter(|segment| include_diagonal || segment.is_axis_aligned())
+        .collect::<Vec<_>>();
 
-    // display_points(segments, 1024, 1024);
+    let mut crossings = HashSet::new();
+    let mut collinear: HashMap<Line, Vec<Segment<i64>>> = HashMap::new();
 
-    let intersections = map_intersections(segments, true);
+    for (i, a) in segments.iter().enumerate() {
+        for b in &segments[i + 1..] {
+            match a.intersection(b) {
+                Some(Intersection::Point(point)) => {
+                    crossings.insert(point);
+                }
+                Some(Intersection::Overlap(overlap)) => {
+                    collinear.entry(Line::of(&overlap)).or_default().push(overlap)
+                }
+                None => {}
+            }
+        }
+    }
 
-    Ok(intersections.len().to_string())
-}
+    // Overlaps are counted from their length, so each point on a line is only counted once
+    let overlaps = collinear
+        .into_iter()
+        .flat_map(|(line, segments)| line.merge(segments))
+        .collect::<Vec<_>>();
+    let mut count = overlaps.iter().map(|s| s.lattice_count() as usize).sum::<usize>();
 
-#[allow(dead_code)]
-fn display_points<I>(segments: I, width: i64, height: i64, include_diagonal: bool)
-where
-    I: IntoIterator<Item = LineSegment>,
-{
-    let mut buffer = vec![0; (width * height) as usize];
+    // Overlaps on different lines which cross were counted once for each line
+    let mut shared = HashSet::new();
 
-    for segment in segments {
-        for point in segment.points(include_diagonal) {
-            buffer[(point.y * width + point.x) as usize] += 1;
+    for (i, a) in overlaps.iter().enumerate() {
+        for b in &overlaps[i + 1..] {
+            if let Some(Intersection::Point(point)) = a.intersection(b) {
+                shared.insert(point);
+            }
         }
     }
 
-    for row in buffer.into_iter().chunks(width as usize).into_iter() {
-        println!("{}", row.map(|n| n.to_string()).join(""));
+    for point in shared {
+        count -= overlaps.iter().filter(|s| s.contains(&point)).count() - 1;
     }
+
+    count
+        + crossings
+            .iter()
+            .filter(|point| !overlaps.iter().any(|s| s.contains(point)))
+            .count()
+}
+
+fn display_points<I>(segments: I, include_diagonal: bool)
+where
+    I: IntoIterator<Item = LineSegment>,
+{
+    eprintln!("{}", count_points(segments, include_diagonal));
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-
-    const TEST_INPUT: [&'static str; 10] = [
-        "0,9 -> 5,9",
-        "8,0 -> 0,8",
-        "9,4 -> 3,4",
-        "2,2 -> 2,1",
-        "7,0 -> 7,4",
-        "6,4 -> 2,0",
-        "0,9 -> 2,9",
-        "3,4 -> 1,4",
-        "0,0 -> 8,8",
-        "5,5 -> 8,2",
-    ];
+    use crate::{example, input, Day};
 
     #[tracing_test::traced_test]
     #[test]
     fn test_part_one() -> Result<()> {
-        let segments = TEST_INPUT
-            .iter()
-            .map(|s| s.parse::<LineSegment>())
-            .collect::<Result<Vec<_>>>()?;
+        let segments = Solver::parse(example(Day::day05).unwrap())?;
 
         assert_eq!(
             segments[0],
@@ -279,7 +310,19 @@ mod test {
         let intersections = map_intersections(segments.clone(), false);
         tracing::debug!(intersections=?intersections);
 
-        display_points(segments, 10, 10, false);
+        assert_eq!(
+            count_points(segments, false).to_string(),
+            ".......1..
+..1....1..
+..1....1..
+.......1..
+.112111211
+..........
+..........
+..........
+..........
+222111...."
+        );
 
         assert_eq!(intersections.len(), 5);
 
@@ -289,10 +332,7 @@ mod test {
     #[tracing_test::traced_test]
     #[test]
     fn test_part_two() -> Result<()> {
-        let segments = TEST_INPUT
-            .iter()
-            .map(|s| s.parse::<LineSegment>())
-            .collect::<Result<Vec<_>>>()?;
+        let segments = Solver::parse(example(Day::day05).unwrap())?;
 
         assert_eq!(
             segments[0],
@@ -307,14 +347,131 @@ mod test {
             LineSegment::new(Point::new(9, 7), Point::new(7, 9)).points(true),
             vec![Point::new(9, 7), Point::new(8, 8), Point::new(7, 9),]
         );
+        assert_eq!(
+            LineSegment::new(Point::new(0, 0), Point::new(4, 2)).points(true),
+            vec![Point::new(0, 0), Point::new(2, 1), Point::new(4, 2)]
+        );
+        assert!(LineSegment::new(Point::new(0, 0), Point::new(4, 2))
+            .points(false)
+            .is_empty());
 
         let intersections = map_intersections(segments.clone(), true);
         tracing::debug!(intersections=?intersections);
 
-        display_points(segments, 10, 10, true);
+        assert_eq!(
+            count_points(segments, true).to_string(),
+            "1.1....11.
+.111...2..
+..2.1.111.
+...1.2.2..
+.112313211
+...1.2....
+..1...1...
+.1.....1..
+1.......1.
+222111...."
+        );
 
         assert_eq!(intersections.len(), 12);
 
         Ok(())
     }
+
+    #[test]
+    fn test_intersect() -> Result<()> {
+        let segments = Solver::parse(example(Day::day05).unwrap())?;
+
+        assert_eq!(count_overlaps(&segments, false), 5);
+        assert_eq!(count_overlaps(&segments, true), 12);
+
+        let segments = Solver::parse(input(Day::day05))?;
+
+        assert_eq!(count_overlaps(&segments, false), 5124);
+        assert_eq!(count_overlaps(&segments, true), 19771);
+
+        let offset = Point::new(3_000_000, 5_000_000);
+        let segments = Solver::parse(example(Day::day05).unwrap())?
+            .into_iter()
+            .map(|s| LineSegment::new(s.start + offset, s.end + offset))
+            .collect::<Vec<_>>();
+
+        assert_eq!(count_overlaps(&segments, true), 12);
+
+        // Long collinear overlaps, horizontal and diagonal, which cross inside both
+        let segments = Solver::parse(
+            "0,2000000 -> 5000000,2000000
+1000000,2000000 -> 9000000,2000000
+0,0 -> 3000000,3000000
+4000000,4000000 -> 1000000,1000000
+2500000,0 -> 2500000,3000000
+3,0 -> 3,9
+0,4 -> 9,4
+0,1 -> 4,3",
+        )?;
+
+        // The horizontal overlap, plus 3,4 where the short segments cross
+        assert_eq!(count_overlaps(&segments, false), 4_000_001 + 1);
+        // Both overlaps sharing 2000000,2000000, plus the diagonal crossing the short
+        // segments at 2,2, 3,3 and 4,4
+        assert_eq!(
+            count_overlaps(&segments, true),
+            4_000_001 + 2_000_001 - 1 + 1 + 3
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_algorithms_agree() -> Result<()> {
+        let inputs = [
+            "0,0 -> 4,2\n0,1 -> 4,1\n",
+            "8,3 -> 8,3\n0,9 -> 0,9\n",
+            "8,3 -> 8,3\n8,3 -> 8,3\n",
+            "8,3 -> 8,3\n0,9 -> 0,9\n8,3 -> 8,3\n2,2 -> 2,2\n0,9 -> 0,9\n",
+            "0,0 -> 4,2
+2,1 -> 8,4
+6,3 -> 0,0
+0,1 -> 8,1
+6,0 -> 6,6
+0,3 -> 8,3
+4,0 -> 4,9
+0,0 -> 8,8
+9,9 -> 3,3
+0,6 -> 9,3
+3,5 -> 6,4
+1,1 -> 1,1
+5,5 -> 5,5",
+            example(Day::day05).unwrap(),
+        ];
+
+        for input in inputs {
+            let segments = Solver::parse(input)?;
+
+            for part in Part::ALL {
+                let rasterise = Solver::solve(&segments, part, &Options::default())?;
+                let intersect = Solver::solve(
+                    &segments,
+                    part,
+                    &Options {
+                        algorithm: Algorithm::Intersect,
+                        ..Default::default()
+                    },
+                )?;
+
+                assert_eq!(rasterise, intersect, "{} of {:?}", part, input);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Solver::parse("0,9 -> 5,9\n8,0 -> 0,8\n9,4 - 3,4\n").unwrap_err();
+
+        assert_eq!(
+            format!("{:#}", error),
+            "Invalid value '9,4 - 3,4' at line 3, column 1: Expected '{start.x},{start.y} -> {end.x},{end.y}'"
+        );
+    }
 }
@@ -1,22 +1,26 @@
 use std::collections::HashMap;
 
-use anyhow::{Context, Result};
-use clap::Parser;
+use anyhow::Result;
 
-use crate::{input, Command};
+use crate::parse;
+use crate::{Answer, NoOptions, Solution};
 
-#[derive(Debug, Parser)]
-pub enum Args {
-    Part1,
-    Part2,
-}
+pub struct Solver;
 
-impl Command for Args {
-    fn execute(&self) -> Result<String> {
-        match self {
-            Self::Part1 => part_one(),
-            Self::Part2 => part_two(),
-        }
+impl Solution for Solver {
+    type Input = Vec<u8>;
+    type Options = NoOptions;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::comma_separated(input)
+    }
+
+    fn part_one(fish: &Self::Input) -> Result<Answer> {
+        Ok(iterate_lantern_fish(fish.iter().copied(), 80).into())
+    }
+
+    fn part_two(fish: &Self::Input) -> Result<Answer> {
+        Ok(iterate_lantern_fish(fish.iter().copied(), 256).into())
     }
 }
 
@@ -54,49 +58,18 @@ where
     counts.values().sum()
 }
 
-fn part_one() -> Result<String> {
-    Ok(iterate_lantern_fish(
-        input(crate::Day::day06)
-            .trim()
-            .split(',')
-            .map(|l| {
-                l.parse::<u8>()
-                    .map_err(anyhow::Error::from)
-                    .with_context(|| format!("Invalid digit: '{}'", l))
-            })
-            .collect::<Result<Vec<_>>>()?,
-        80,
-    )
-    .to_string())
-}
-
-fn part_two() -> Result<String> {
-    Ok(iterate_lantern_fish(
-        input(crate::Day::day06)
-            .trim()
-            .split(',')
-            .map(|l| {
-                l.parse::<u8>()
-                    .map_err(anyhow::Error::from)
-                    .with_context(|| format!("Invalid digit: '{}'", l))
-            })
-            .collect::<Result<Vec<_>>>()?,
-        256,
-    )
-    .to_string())
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
-
-    const TEST_INPUT: [u8; 5] = [3, 4, 3, 1, 2];
+    use crate::{example, Day};
 
     #[tracing_test::traced_test]
     #[test]
     fn test_part_one() -> Result<()> {
-        assert_eq!(iterate_lantern_fish(TEST_INPUT.to_vec(), 18), 26);
-        assert_eq!(iterate_lantern_fish(TEST_INPUT.to_vec(), 80), 5934);
+        let fish = Solver::parse(example(Day::day06).unwrap())?;
+
+        assert_eq!(iterate_lantern_fish(fish.clone(), 18), 26);
+        assert_eq!(iterate_lantern_fish(fish, 80), 5934);
 
         Ok(())
     }
@@ -104,8 +77,10 @@ mod test {
     #[tracing_test::traced_test]
     #[test]
     fn test_part_two() -> Result<()> {
+        let fish = Solver::parse(example(Day::day06).unwrap())?;
+
         assert_eq!(
-            iterate_lantern_fish(TEST_INPUT.to_vec(), 256),
+            iterate_lantern_fish(fish, 256),
             26_984_457_539
         );
         Ok(())
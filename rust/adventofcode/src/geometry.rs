@@ -0,0 +1,344 @@
+use std::iter;
+
+use crate::numeric::Numeric;
+use crate::point::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// Both ends are the same point
+    Point,
+    Horizontal,
+    Vertical,
+    /// Exactly 45 degrees
+    Diagonal,
+    Sloped,
+}
+
+/// A line segment including both of its ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment<T> {
+    pub start: Point<T>,
+    pub end: Point<T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intersection {
+    Point(Point<i64>),
+    /// Collinear segments sharing more than one point
+    Overlap(Segment<i64>),
+}
+
+impl<T> Segment<T>
+where
+    T: Numeric,
+{
+    pub fn new(start: Point<T>, end: Point<T>) -> Self {
+        Self { start, end }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        let dx = self.start.x.distance(self.end.x);
+        let dy = self.start.y.distance(self.end.y);
+
+        if dx == T::zero() && dy == T::zero() {
+            Orientation::Point
+        } else if dy == T::zero() {
+            Orientation::Horizontal
+        } else if dx == T::zero() {
+            Orientation::Vertical
+        } else if dx == dy {
+            Orientation::Diagonal
+        } else {
+            Orientation::Sloped
+        }
+    }
+
+    pub fn is_axis_aligned(&self) -> bool {
+        matches!(
+            self.orientation(),
+            Orientation::Point | Orientation::Horizontal | Orientation::Vertical
+        )
+    }
+
+    pub fn reversed(&self) -> Self {
+        Self::new(self.end, self.start)
+    }
+}
+
+fn cross(a: Point<i128>, b: Point<i128>) -> i128 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Point<i128>, b: Point<i128>) -> i128 {
+    a.x * b.x + a.y * b.y
+}
+
+fn widen(p: Point<i64>) -> Point<i128> {
+    Point::new(p.x as i128, p.y as i128)
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Segment<i64> {
+    /// Bresenham rasterisation from `start` to `end`, for any slope
+    #[allow(dead_code)]
+    pub fn points(&self) -> impl Iterator<Item = Point<i64>> {
+        let end = self.end;
+        let dx = (end.x - self.start.x).abs();
+        let dy = -(end.y - self.start.y).abs();
+        let step = (end - self.start).signum();
+
+        let mut current = Some(self.start);
+        let mut error = dx + dy;
+
+        iter::from_fn(move || {
+            let point = current?;
+
+            current = if point == end {
+                None
+            } else {
+                let mut next = point;
+                let doubled = 2 * error;
+
+                if doubled >= dy {
+                    error += dy;
+                    next.x += step.x;
+                }
+                if doubled <= dx {
+                    error += dx;
+                    next.y += step.y;
+                }
+
+                Some(next)
+            };
+
+            Some(point)
+        })
+    }
+
+    /// Only the integer points lying exactly on the segment, which for sloped
+    /// segments is fewer than `points` draws
+    pub fn lattice_points(&self) -> impl Iterator<Item = Point<i64>> {
+        let delta = self.end - self.start;
+        let steps = gcd(delta.x, delta.y);
+        let step = if steps == 0 { delta } else { delta / steps };
+        let start = self.start;
+
+        (0..=steps).map(move |n| start + step * n)
+    }
+
+    /// How many points `lattice_points` yields, without generating them
+    pub fn lattice_count(&self) -> u64 {
+        let delta = self.end - self.start;
+
+        gcd(delta.x, delta.y).unsigned_abs() + 1
+    }
+
+    /// The step between lattice points, pointing towards positive x (or positive y when
+    /// vertical) so every collinear segment has the same one. Zero for a single point.
+    pub fn lattice_step(&self) -> Point<i64> {
+        let delta = self.end - self.start;
+        let steps = gcd(delta.x, delta.y);
+
+        match steps {
+            0 => delta,
+            _ if delta.x < 0 || (delta.x == 0 && delta.y < 0) => delta / -steps,
+            _ => delta / steps,
+        }
+    }
+
+    pub fn contains(&self, point: &Point<i64>) -> bool {
+        let r = widen(self.end - self.start);
+        let offset = widen(*point - self.start);
+
+        if r == Point::new(0, 0) {
+            return *point == self.start;
+        }
+
+        cross(offset, r) == 0 && (0..=dot(r, r)).contains(&dot(offset, r))
+    }
+
+    /// Where the two segments meet, computed exactly rather than by rasterising.
+    /// A crossing which falls between integer points isn't reported.
+    pub fn intersection(&self, other: &Self) -> Option<Intersection> {
+        let p = widen(self.start);
+        let r = widen(self.end - self.start);
+        let q = widen(other.start);
+        let s = widen(other.end - other.start);
+
+        let denominator = cross(r, s);
+        let qp = q - p;
+
+        if denominator != 0 {
+            // t and u are the fractions along self and other, with the denominator made positive
+            let sign = denominator.signum();
+            let (t, u, denominator) =
+                (cross(qp, s) * sign, cross(qp, r) * sign, denominator * sign);
+
+            if !(0..=denominator).contains(&t) || !(0..=denominator).contains(&u) {
+                return None;
+            }
+
+            let (x, y) = (r.x * t, r.y * t);
+
+            if x % denominator != 0 || y % denominator != 0 {
+                return None;
+            }
+
+            return Some(Intersection::Point(Point::new(
+                (p.x + x / denominator) as i64,
+                (p.y + y / denominator) as i64,
+            )));
+        }
+
+        if cross(qp, r) != 0 {
+            return None;
+        }
+
+        // Collinear, or one of them is a single point
+        if r == Point::new(0, 0) {
+            return other
+                .contains(&self.start)
+                .then_some(Intersection::Point(self.start));
+        }
+        if s == Point::new(0, 0) {
+            return self
+                .contains(&other.start)
+                .then_some(Intersection::Point(other.start));
+        }
+
+        // Project everything onto self, where start is 0 and end is |r|^2
+        let along = |point: Point<i64>| dot(widen(point) - p, r);
+        let (low, high) = if along(other.start) <= along(other.end) {
+            (other.start, other.end)
+        } else {
+            (other.end, other.start)
+        };
+
+        let start = if along(low) > 0 { low } else { self.start };
+        let end = if along(high) < dot(r, r) {
+            high
+        } else {
+            self.end
+        };
+
+        match along(start).cmp(&along(end)) {
+            std::cmp::Ordering::Less => Some(Intersection::Overlap(Segment::new(start, end))),
+            std::cmp::Ordering::Equal => Some(Intersection::Point(start)),
+            std::cmp::Ordering::Greater => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn segment(x1: i64, y1: i64, x2: i64, y2: i64) -> Segment<i64> {
+        Segment::new(Point::new(x1, y1), Point::new(x2, y2))
+    }
+
+    #[test]
+    fn test_orientation() {
+        assert_eq!(segment(1, 1, 1, 1).orientation(), Orientation::Point);
+        assert_eq!(segment(0, 9, 5, 9).orientation(), Orientation::Horizontal);
+        assert_eq!(segment(7, 0, 7, 4).orientation(), Orientation::Vertical);
+        assert_eq!(segment(8, 0, 0, 8).orientation(), Orientation::Diagonal);
+        assert_eq!(segment(0, 0, 4, 2).orientation(), Orientation::Sloped);
+        assert!(segment(7, 0, 7, 4).is_axis_aligned());
+        assert!(!segment(8, 0, 0, 8).is_axis_aligned());
+        assert_eq!(
+            Segment::new(Point::new(5u64, 1), Point::new(2, 4)).orientation(),
+            Orientation::Diagonal
+        );
+    }
+
+    #[test]
+    fn test_points() {
+        assert_eq!(
+            segment(9, 7, 7, 9).points().collect::<Vec<_>>(),
+            vec![Point::new(9, 7), Point::new(8, 8), Point::new(7, 9)]
+        );
+        assert_eq!(
+            segment(0, 9, 3, 9).points().collect::<Vec<_>>(),
+            segment(0, 9, 3, 9).lattice_points().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            segment(0, 0, 4, 2).points().collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(3, 2),
+                Point::new(4, 2)
+            ]
+        );
+        assert_eq!(
+            segment(0, 0, 4, 2).lattice_points().collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(2, 1), Point::new(4, 2)]
+        );
+        assert_eq!(segment(2, 3, 2, 3).points().count(), 1);
+        assert_eq!(segment(2, 3, 2, 3).lattice_points().count(), 1);
+        assert_eq!(segment(0, 0, -3, 7).points().count(), 8);
+        assert_eq!(segment(0, 0, 4, 2).lattice_count(), 3);
+        assert_eq!(segment(2, 3, 2, 3).lattice_count(), 1);
+        assert_eq!(segment(0, 0, 0, -4_000_000).lattice_count(), 4_000_001);
+        assert_eq!(segment(4, 2, 0, 0).lattice_step(), Point::new(2, 1));
+        assert_eq!(segment(0, 0, 4, 2).lattice_step(), Point::new(2, 1));
+        assert_eq!(segment(0, 9, 0, 3).lattice_step(), Point::new(0, 1));
+        assert_eq!(segment(3, 0, 0, 3).lattice_step(), Point::new(1, -1));
+    }
+
+    #[test]
+    fn test_contains() {
+        assert!(segment(0, 0, 4, 2).contains(&Point::new(2, 1)));
+        assert!(segment(0, 0, 4, 2).contains(&Point::new(4, 2)));
+        assert!(!segment(0, 0, 4, 2).contains(&Point::new(1, 0)));
+        assert!(!segment(0, 0, 4, 2).contains(&Point::new(6, 3)));
+        assert!(segment(1, 1, 1, 1).contains(&Point::new(1, 1)));
+        assert!(!segment(1, 1, 1, 1).contains(&Point::new(2, 2)));
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(
+            segment(0, 0, 4, 4).intersection(&segment(0, 4, 4, 0)),
+            Some(Intersection::Point(Point::new(2, 2)))
+        );
+        assert_eq!(
+            segment(0, 9, 5, 9).intersection(&segment(2, 0, 2, 9)),
+            Some(Intersection::Point(Point::new(2, 9)))
+        );
+        assert_eq!(segment(0, 0, 1, 1).intersection(&segment(1, 0, 0, 1)), None);
+        assert_eq!(segment(0, 0, 4, 0).intersection(&segment(0, 1, 4, 1)), None);
+        assert_eq!(segment(0, 0, 4, 0).intersection(&segment(5, 0, 9, 0)), None);
+        assert_eq!(
+            segment(0, 0, 4, 0).intersection(&segment(4, 0, 9, 0)),
+            Some(Intersection::Point(Point::new(4, 0)))
+        );
+        assert_eq!(
+            segment(0, 9, 5, 9).intersection(&segment(7, 9, 2, 9)),
+            Some(Intersection::Overlap(segment(2, 9, 5, 9)))
+        );
+        assert_eq!(
+            segment(6, 6, 0, 0).intersection(&segment(2, 2, 3, 3)),
+            Some(Intersection::Overlap(segment(3, 3, 2, 2)))
+        );
+        assert_eq!(
+            segment(3, 3, 3, 3).intersection(&segment(0, 0, 5, 5)),
+            Some(Intersection::Point(Point::new(3, 3)))
+        );
+        assert_eq!(segment(0, 0, 5, 5).intersection(&segment(6, 6, 6, 6)), None);
+        assert_eq!(segment(1, 1, 1, 1).intersection(&segment(5, 5, 5, 5)), None);
+        assert_eq!(
+            segment(1, 1, 1, 1).intersection(&segment(1, 1, 1, 1)),
+            Some(Intersection::Point(Point::new(1, 1)))
+        );
+    }
+}
@@ -0,0 +1,548 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops;
+
+use anyhow::{anyhow, ensure, Result};
+
+use crate::point::{Bounds, Point};
+
+/// A dense, row-major grid of cells addressed by `Point<i64>` with (0, 0) in the top left
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Clone + Default,
+    {
+        Self::filled(width, height, T::default())
+    }
+
+    /// Fails if the rows aren't all the same length
+    pub fn from_rows<I, R>(rows: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for row in rows {
+            let before = cells.len();
+            cells.extend(row);
+
+            let len = cells.len() - before;
+            let width = *width.get_or_insert(len);
+
+            ensure!(
+                len == width,
+                "Row {} has {} cells, expected {}",
+                height,
+                len,
+                width
+            );
+
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a block of text with one cell per character
+    pub fn parse_with<F>(s: &str, mut parse_cell: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        Self::from_rows(
+            s.lines()
+                .enumerate()
+                .map(|(y, line)| {
+                    line.chars()
+                        .enumerate()
+                        .map(|(x, c)| {
+                            parse_cell(c).map_err(|e| {
+                                e.context(format!(
+                                    "Invalid cell at line {}, column {}",
+                                    y + 1,
+                                    x + 1
+                                ))
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()?,
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Bounds<i64> {
+        Bounds::new(
+            Point::new(0, 0),
+            Point::new(self.width as i64 - 1, self.height as i64 - 1),
+        )
+    }
+
+    pub fn contains(&self, point: &Point<i64>) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    fn index_of(&self, point: &Point<i64>) -> Option<usize> {
+        if point.x < 0 || point.y < 0 {
+            return None;
+        }
+
+        let (x, y) = (point.x as usize, point.y as usize);
+
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn point_of(&self, index: usize) -> Point<i64> {
+        Point::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn get(&self, point: &Point<i64>) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: &Point<i64>) -> Option<&mut T> {
+        self.index_of(point)
+            .map(move |index| &mut self.cells[index])
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i64>, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| (self.point_of(index), cell))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point<i64>, &mut T)> {
+        let width = self.width;
+
+        self.cells.iter_mut().enumerate().map(move |(index, cell)| {
+            (
+                Point::new((index % width) as i64, (index / width) as i64),
+                cell,
+            )
+        })
+    }
+
+    pub fn position<P>(&self, predicate: P) -> Option<Point<i64>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.point_of(index))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} out of range", x);
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn rebuild<F>(&self, width: usize, height: usize, source: F) -> Self
+    where
+        T: Clone,
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (x, y) = source(x, y);
+                self.cells[y * self.width + x].clone()
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+
+        self.rebuild(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+
+        self.rebuild(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+}
+
+#[allow(dead_code)]
+impl Grid<char> {
+    pub fn parse_chars(s: &str) -> Result<Self> {
+        Self::parse_with(s, Ok)
+    }
+}
+
+#[allow(dead_code)]
+impl Grid<u32> {
+    pub fn parse_digits(s: &str) -> Result<Self> {
+        Self::parse_with(s, |c| {
+            c.to_digit(10)
+                .ok_or_else(|| anyhow!("'{}' is not a digit", c))
+        })
+    }
+}
+
+impl<T> ops::Index<Point<i64>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<i64>) -> &T {
+        self.get(&point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T> ops::IndexMut<Point<i64>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<i64>) -> &mut T {
+        self.get_mut(&point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T> fmt::Display for Grid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A grid of cells keyed by `Point<i64>` that can grow in any direction, keeping
+/// track of the bounding box of every point inserted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point<i64>, T>,
+    bounds: Option<Bounds<i64>>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn include(&mut self, point: Point<i64>) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(&point),
+            None => self.bounds = Some(Bounds::around(point)),
+        }
+    }
+
+    pub fn insert(&mut self, point: Point<i64>, value: T) -> Option<T> {
+        self.include(point);
+        self.cells.insert(point, value)
+    }
+
+    pub fn entry_or_default(&mut self, point: Point<i64>) -> &mut T
+    where
+        T: Default,
+    {
+        self.include(point);
+        self.cells.entry(point).or_default()
+    }
+
+    pub fn get(&self, point: &Point<i64>) -> Option<&T> {
+        self.cells.get(point)
+    }
+
+    pub fn get_mut(&mut self, point: &Point<i64>) -> Option<&mut T> {
+        self.cells.get_mut(point)
+    }
+
+    pub fn contains(&self, point: &Point<i64>) -> bool {
+        self.cells.contains_key(point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// None until something has been inserted
+    pub fn bounds(&self) -> Option<Bounds<i64>> {
+        self.bounds
+    }
+
+    /// Cells in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i64>, &T)> {
+        self.cells.iter().map(|(point, cell)| (*point, cell))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point<i64>> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Renders the bounding box row by row, using `empty` for points with no cell
+    pub fn render<F>(&self, empty: char, mut render_cell: F) -> String
+    where
+        F: FnMut(&T) -> String,
+    {
+        let bounds = match self.bounds {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| match self.get(&Point::new(x, y)) {
+                        Some(cell) => render_cell(cell),
+                        None => empty.to_string(),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Counts how many times each point occurs
+impl FromIterator<Point<i64>> for SparseGrid<usize> {
+    fn from_iter<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = Point<i64>>,
+    {
+        let mut grid = Self::new();
+
+        for point in points {
+            *grid.entry_or_default(point) += 1;
+        }
+
+        grid
+    }
+}
+
+impl<T> fmt::Display for SparseGrid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render('.', |cell| cell.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BLOCK: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_grid_parse() -> Result<()> {
+        let grid = Grid::parse_chars(BLOCK)?;
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Point::new(1, 1)], 'e');
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse_digits("123\n456")?;
+        assert_eq!(digits[Point::new(2, 0)], 3);
+        assert_eq!(digits.cells().sum::<u32>(), 21);
+
+        assert!(Grid::parse_digits("12\n3x").is_err());
+        assert!(Grid::parse_chars("abc\nde").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid_access() -> Result<()> {
+        let mut grid = Grid::parse_chars(BLOCK)?;
+
+        assert!(grid.contains(&Point::new(2, 1)));
+        assert!(!grid.contains(&Point::new(3, 1)));
+        assert!(!grid.contains(&Point::new(-1, 0)));
+        assert_eq!(grid.get(&Point::new(0, 2)), None);
+        assert_eq!(
+            grid.bounds(),
+            Bounds::new(Point::new(0, 0), Point::new(2, 1))
+        );
+
+        grid[Point::new(0, 1)] = 'x';
+        *grid.get_mut(&Point::new(2, 0)).unwrap() = 'y';
+        assert_eq!(grid.to_string(), "aby\nxef");
+        assert_eq!(grid.position(|c| *c == 'x'), Some(Point::new(0, 1)));
+
+        assert_eq!(grid.iter().map(|(p, _)| p).last(), Some(Point::new(2, 1)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid_rows_columns() -> Result<()> {
+        let grid = Grid::parse_chars(BLOCK)?;
+
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid_transform() -> Result<()> {
+        let grid = Grid::parse_chars(BLOCK)?;
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new();
+
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+
+        grid.insert(Point::new(2, -1), 'a');
+        grid.insert(Point::new(-1, 1), 'b');
+        assert_eq!(grid.insert(Point::new(2, -1), 'c'), Some('a'));
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(&Point::new(2, -1)), Some(&'c'));
+        assert!(!grid.contains(&Point::new(0, 0)));
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds::new(Point::new(-1, -1), Point::new(2, 1)))
+        );
+        assert_eq!(grid.to_string(), "...c\n....\nb...");
+        assert_eq!(
+            grid.render(' ', |c| c.to_ascii_uppercase().to_string()),
+            "   C\n    \nB   "
+        );
+    }
+
+    #[test]
+    fn test_sparse_grid_count() {
+        let grid = vec![Point::new(0, 0), Point::new(1, 1), Point::new(0, 0)]
+            .into_iter()
+            .collect::<SparseGrid<usize>>();
+
+        assert_eq!(grid.get(&Point::new(0, 0)), Some(&2));
+        assert_eq!(grid.iter().filter(|(_, count)| **count > 1).count(), 1);
+        assert_eq!(grid.to_string(), "2.\n.1");
+    }
+}
@@ -1,62 +1,82 @@
+mod answer;
 #[macro_use]
 mod args;
+mod bench;
 mod command;
+mod geometry;
+mod grid;
+mod matrix;
+mod numeric;
+mod output;
+mod parse;
 mod point;
+mod runner;
+mod scaffold;
+mod solution;
+mod source;
+mod util;
+mod verify;
 
+use adventofcode_derive::PuzzleParse;
 use anyhow::Result;
 use clap::Parser;
 use tracing_subscriber::fmt::format::FmtSpan;
 
-use crate::command::Command;
+use crate::answer::Answer;
+use crate::args::Action;
+use crate::command::Part;
+use crate::parse::ParseError;
+use crate::solution::{NoOptions, Solution};
 
 #[allow(unused_imports)]
-use crate::point::{Dimension, DimensionedValue, Point};
+use crate::geometry::{Intersection, Orientation, Segment};
+#[allow(unused_imports)]
+use crate::grid::{Grid, SparseGrid};
+#[allow(unused_imports)]
+use crate::matrix::{Matrix2, Matrix3};
+#[allow(unused_imports)]
+use crate::point::{Dimension, Dimensioned, DimensionedValue, Point};
+#[allow(unused_imports)]
+use crate::util::InclusiveRange;
 
-macro_rules! solution {
-    ($($day:ident),+) => {
-        $(
-            mod $day;
-        )+
+// Generated by build.rs from the dayNN.rs files in src/: the day modules, `Day`,
+// `Solutions` and the embedded `input` for each day.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
 
-        pub enum Day {
-            $(
-            #[allow(non_camel_case_types)]
-            $day,
-            )+
-        }
+impl std::str::FromStr for Day {
+    type Err = anyhow::Error;
 
-        #[derive(Debug, Parser)]
-        pub enum Solutions {
-            $(
-            #[allow(non_camel_case_types)]
-            $day {
-                #[clap(subcommand)]
-                contents: crate::$day::Args,
-            },
-            )+
-        }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s
+            .to_lowercase()
+            .trim_start_matches("day")
+            .parse::<u32>()
+            .map_err(|_| anyhow::anyhow!("Unrecognized day: '{}'", s))?;
 
-        fn input(day: Day) -> &'static str {
-            match day {
-                $(Day::$day { .. } => include_str!(concat!("../../../inputs/", stringify!($day), ".txt")),)+
-            }
-        }
+        Day::ALL
+            .iter()
+            .copied()
+            .find(|day| day.number() == number)
+            .ok_or_else(|| anyhow::anyhow!("Day {} has no solution", number))
+    }
+}
 
-        // stringify!($day)
-        impl Command for Solutions {
-            fn execute(&self) -> anyhow::Result<String> {
-                match self {
-                    $(Self::$day { contents } => contents.execute(),)+
-                }
-            }
+fn main() {
+    if let Err(e) = run() {
+        match e
+            .chain()
+            .filter_map(|e| e.downcast_ref::<ParseError>())
+            .last()
+        {
+            Some(parse_error) => eprintln!("{}", parse_error.render()),
+            None => eprintln!("Error: {:?}", e),
         }
+
+        std::process::exit(1);
     }
 }
 
-// NOTE: Each solution module must be added here
-solution!(day01, day02, day03, day04, day05, day06);
-
-fn main() -> Result<()> {
+fn run() -> Result<()> {
     let args = args::Args::parse();
 
     tracing_subscriber::fmt()
@@ -65,9 +85,11 @@ fn main() -> Result<()> {
         .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE)
         .init();
 
-    let solution = args.command.execute()?;
-
-    println!("Solution:\n{}", solution);
-
-    Ok(())
+    match &args.command {
+        Action::All(all) => runner::run_all(all, &args.shared_input_source("all")?, args.format),
+        Action::Bench(bench) => bench::run_bench(bench, &args.input_source(), args.format),
+        Action::Verify(verify) => verify::run_verify(verify, &args.shared_input_source("verify")?),
+        Action::NewDay(new_day) => scaffold::run_new_day(new_day),
+        Action::Solution(solution) => runner::run_one(solution, &args.input_source(), args.format),
+    }
 }
@@ -0,0 +1,228 @@
+use std::ops;
+
+use crate::point::{Point, Point3};
+
+/// A 2x2 matrix stored as rows, for transforming `Point`s
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix2<T>(pub [[T; 2]; 2]);
+
+/// A 3x3 matrix stored as rows, for transforming `Point3`s
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix3<T>(pub [[T; 3]; 3]);
+
+#[allow(dead_code)]
+impl Matrix2<i64> {
+    pub const IDENTITY: Self = Matrix2([[1, 0], [0, 1]]);
+
+    /// Same as `Point::rotate_clockwise`, with y increasing downwards
+    pub const ROTATE_CLOCKWISE: Self = Matrix2([[0, -1], [1, 0]]);
+    pub const ROTATE_COUNTERCLOCKWISE: Self = Matrix2([[0, 1], [-1, 0]]);
+
+    pub const REFLECT_X: Self = Matrix2([[-1, 0], [0, 1]]);
+    pub const REFLECT_Y: Self = Matrix2([[1, 0], [0, -1]]);
+}
+
+#[allow(dead_code)]
+impl Matrix3<i64> {
+    pub const IDENTITY: Self = Matrix3([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// Quarter turns around each axis, same as `Point3::rotate_x` etc.
+    pub const ROTATE_X: Self = Matrix3([[1, 0, 0], [0, 0, -1], [0, 1, 0]]);
+    pub const ROTATE_Y: Self = Matrix3([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]);
+    pub const ROTATE_Z: Self = Matrix3([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+
+    /// The 24 rotations of a cube: x can face any of 6 directions, then there
+    /// are 4 quarter turns around it
+    pub fn orientations() -> Vec<Self> {
+        let facings = [
+            Self::IDENTITY,
+            Self::ROTATE_Y,
+            Self::ROTATE_Y * Self::ROTATE_Y,
+            Self::ROTATE_Y * Self::ROTATE_Y * Self::ROTATE_Y,
+            Self::ROTATE_Z,
+            Self::ROTATE_Z * Self::ROTATE_Z * Self::ROTATE_Z,
+        ];
+
+        facings
+            .iter()
+            .flat_map(|facing| {
+                (0..4).scan(*facing, |m, _| {
+                    let current = *m;
+                    *m = *m * Self::ROTATE_X;
+                    Some(current)
+                })
+            })
+            .collect()
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Matrix2<T>
+where
+    T: Copy + ops::Mul<Output = T> + ops::Sub<Output = T>,
+{
+    pub fn determinant(&self) -> T {
+        let [[a, b], [c, d]] = self.0;
+
+        a * d - b * c
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Matrix3<T>
+where
+    T: Copy + ops::Mul<Output = T> + ops::Add<Output = T> + ops::Sub<Output = T>,
+{
+    pub fn determinant(&self) -> T {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.0;
+
+        a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Matrix2<T>
+where
+    T: Copy,
+{
+    pub fn transpose(&self) -> Self {
+        let [[a, b], [c, d]] = self.0;
+
+        Matrix2([[a, c], [b, d]])
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Matrix3<T>
+where
+    T: Copy,
+{
+    pub fn transpose(&self) -> Self {
+        let m = self.0;
+
+        Matrix3([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+}
+
+impl<T> ops::Mul<Point<T>> for Matrix2<T>
+where
+    T: Copy + ops::Mul<Output = T> + ops::Add<Output = T>,
+{
+    type Output = Point<T>;
+
+    fn mul(self, p: Point<T>) -> Point<T> {
+        let [[a, b], [c, d]] = self.0;
+
+        Point::new(a * p.x + b * p.y, c * p.x + d * p.y)
+    }
+}
+
+impl<T> ops::Mul<Point3<T>> for Matrix3<T>
+where
+    T: Copy + ops::Mul<Output = T> + ops::Add<Output = T>,
+{
+    type Output = Point3<T>;
+
+    fn mul(self, p: Point3<T>) -> Point3<T> {
+        let [x, y, z] = self.0.map(|[a, b, c]| a * p.x + b * p.y + c * p.z);
+
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T> ops::Mul<Matrix2<T>> for Matrix2<T>
+where
+    T: Copy + ops::Mul<Output = T> + ops::Add<Output = T>,
+{
+    type Output = Matrix2<T>;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.0, rhs.0);
+
+        Matrix2([0, 1].map(|i| [0, 1].map(|j| a[i][0] * b[0][j] + a[i][1] * b[1][j])))
+    }
+}
+
+impl<T> ops::Mul<Matrix3<T>> for Matrix3<T>
+where
+    T: Copy + ops::Mul<Output = T> + ops::Add<Output = T>,
+{
+    type Output = Matrix3<T>;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.0, rhs.0);
+
+        Matrix3(
+            [0, 1, 2].map(|i| {
+                [0, 1, 2].map(|j| a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j])
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::point::Dimension;
+
+    #[test]
+    fn test_matrix2() {
+        let p = Point::new(3, 1);
+
+        assert_eq!(Matrix2::IDENTITY * p, p);
+        assert_eq!(Matrix2::ROTATE_CLOCKWISE * p, p.rotate_clockwise());
+        assert_eq!(
+            Matrix2::ROTATE_COUNTERCLOCKWISE * p,
+            p.rotate_counterclockwise()
+        );
+        assert_eq!(Matrix2::REFLECT_X * p, p.reflect(Dimension::X));
+        assert_eq!(Matrix2::REFLECT_Y * p, p.reflect(Dimension::Y));
+        assert_eq!(
+            Matrix2::ROTATE_CLOCKWISE * Matrix2::ROTATE_COUNTERCLOCKWISE,
+            Matrix2::IDENTITY
+        );
+        assert_eq!(
+            Matrix2::ROTATE_CLOCKWISE.transpose(),
+            Matrix2::ROTATE_COUNTERCLOCKWISE
+        );
+        assert_eq!(Matrix2::REFLECT_X.determinant(), -1);
+    }
+
+    #[test]
+    fn test_matrix3() {
+        let p = Point3::new(1, 2, 3);
+
+        assert_eq!(Matrix3::IDENTITY * p, p);
+        assert_eq!(Matrix3::ROTATE_X * p, p.rotate_x());
+        assert_eq!(Matrix3::ROTATE_Y * p, p.rotate_y());
+        assert_eq!(Matrix3::ROTATE_Z * p, p.rotate_z());
+        assert_eq!(
+            (Matrix3::ROTATE_Z * Matrix3::ROTATE_X) * p,
+            Matrix3::ROTATE_Z * (Matrix3::ROTATE_X * p)
+        );
+        assert_eq!(
+            Matrix3::ROTATE_Y * Matrix3::ROTATE_Y.transpose(),
+            Matrix3::IDENTITY
+        );
+    }
+
+    #[test]
+    fn test_orientations() {
+        let orientations = Matrix3::orientations();
+
+        assert_eq!(orientations.len(), 24);
+        assert_eq!(orientations[0], Matrix3::IDENTITY);
+        assert!(orientations.iter().all(|m| m.determinant() == 1));
+
+        let p = Point3::new(1, 2, 3);
+        let points = orientations.iter().map(|m| *m * p).collect::<HashSet<_>>();
+
+        assert_eq!(points.len(), 24);
+    }
+}
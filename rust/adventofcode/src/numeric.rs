@@ -0,0 +1,310 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops;
+
+/// The arithmetic shared by every primitive integer and float, so `Point` and friends
+/// work with any of them as coordinates
+#[allow(dead_code)]
+pub trait Numeric:
+    Copy
+    + PartialOrd
+    + Default
+    + fmt::Debug
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Div<Output = Self>
+    + ops::Rem<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    /// Unsigned types are their own absolute value
+    fn abs(self) -> Self;
+
+    /// -1, 0 or 1, with 0 for both signed float zeros
+    fn signum(self) -> Self;
+
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+
+    /// The absolute difference, which can't underflow for unsigned types
+    fn distance(self, other: Self) -> Self;
+
+    /// None if adding `delta` would leave the range of the type
+    fn offset(self, delta: i64) -> Option<Self>;
+
+    fn from_i64(n: i64) -> Option<Self>;
+
+    /// Floats are truncated, None if out of range or not finite
+    fn to_i64(self) -> Option<i64>;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric_integer {
+    (signed: $($t:ty),+) => {
+        $(
+            impl Numeric for $t {
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+
+                impl_numeric_integer!(@common $t);
+            }
+        )+
+    };
+
+    (unsigned: $($t:ty),+) => {
+        $(
+            impl Numeric for $t {
+                fn abs(self) -> Self {
+                    self
+                }
+
+                fn signum(self) -> Self {
+                    if self == 0 {
+                        0
+                    } else {
+                        1
+                    }
+                }
+
+                impl_numeric_integer!(@common $t);
+            }
+        )+
+    };
+
+    (@common $t:ty) => {
+        fn zero() -> Self {
+            0
+        }
+
+        fn one() -> Self {
+            1
+        }
+
+        fn min(self, other: Self) -> Self {
+            Ord::min(self, other)
+        }
+
+        fn max(self, other: Self) -> Self {
+            Ord::max(self, other)
+        }
+
+        fn distance(self, other: Self) -> Self {
+            if self > other {
+                self - other
+            } else {
+                other - self
+            }
+        }
+
+        fn offset(self, delta: i64) -> Option<Self> {
+            match <$t>::try_from(delta.unsigned_abs()) {
+                Ok(magnitude) if delta < 0 => self.checked_sub(magnitude),
+                Ok(magnitude) => self.checked_add(magnitude),
+                Err(_) => <$t>::try_from(delta).ok().and_then(|d| self.checked_add(d)),
+            }
+        }
+
+        fn from_i64(n: i64) -> Option<Self> {
+            <$t>::try_from(n).ok()
+        }
+
+        fn to_i64(self) -> Option<i64> {
+            i64::try_from(self).ok()
+        }
+
+        fn to_f64(self) -> f64 {
+            self as f64
+        }
+    };
+}
+
+macro_rules! impl_numeric_float {
+    ($($t:ty),+) => {
+        $(
+            impl Numeric for $t {
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn one() -> Self {
+                    1.0
+                }
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    if self == 0.0 {
+                        0.0
+                    } else {
+                        <$t>::signum(self)
+                    }
+                }
+
+                fn min(self, other: Self) -> Self {
+                    <$t>::min(self, other)
+                }
+
+                fn max(self, other: Self) -> Self {
+                    <$t>::max(self, other)
+                }
+
+                fn distance(self, other: Self) -> Self {
+                    (self - other).abs()
+                }
+
+                fn offset(self, delta: i64) -> Option<Self> {
+                    Some(self + delta as $t)
+                }
+
+                fn from_i64(n: i64) -> Option<Self> {
+                    Some(n as $t)
+                }
+
+                fn to_i64(self) -> Option<i64> {
+                    if self.is_finite() && self >= i64::MIN as $t && self < i64::MAX as $t {
+                        Some(self as i64)
+                    } else {
+                        None
+                    }
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )+
+    };
+}
+
+impl_numeric_integer!(signed: i8, i16, i32, i64, i128, isize);
+impl_numeric_integer!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_numeric_float!(f32, f64);
+
+/// Overflow-aware arithmetic, forwarded to the inherent methods on the primitive integers
+pub trait CheckedArithmetic: Sized {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_checked_arithmetic {
+    ($($t:ty),+) => {
+        $(
+            impl CheckedArithmetic for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$t>::saturating_sub(self, rhs)
+                }
+
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    <$t>::saturating_mul(self, rhs)
+                }
+
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    <$t>::wrapping_add(self, rhs)
+                }
+
+                fn wrapping_sub(self, rhs: Self) -> Self {
+                    <$t>::wrapping_sub(self, rhs)
+                }
+
+                fn wrapping_mul(self, rhs: Self) -> Self {
+                    <$t>::wrapping_mul(self, rhs)
+                }
+            }
+        )+
+    };
+}
+
+impl_checked_arithmetic!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_numeric_integer() {
+        assert_eq!(i32::zero(), 0);
+        assert_eq!(u8::one(), 1);
+        assert_eq!(Numeric::abs(-5i8), 5);
+        assert_eq!(Numeric::abs(5u16), 5);
+        assert_eq!(Numeric::signum(-5i64), -1);
+        assert_eq!(Numeric::signum(0u64), 0);
+        assert_eq!(Numeric::signum(7usize), 1);
+        assert_eq!(Numeric::min(3u32, 2), 2);
+        assert_eq!(Numeric::max(-3i32, 2), 2);
+        assert_eq!(3u64.distance(7), 4);
+        assert_eq!((-3i64).distance(7), 10);
+    }
+
+    #[test]
+    fn test_numeric_offset() {
+        assert_eq!(5u8.offset(-5), Some(0));
+        assert_eq!(5u8.offset(-6), None);
+        assert_eq!(250u8.offset(6), None);
+        assert_eq!(0u64.offset(i64::MIN), None);
+        assert_eq!(u64::MAX.offset(i64::MIN), Some(u64::MAX / 2));
+        assert_eq!(0i8.offset(-128), Some(-128));
+        assert_eq!(0i8.offset(128), None);
+        assert_eq!(i64::MAX.offset(1), None);
+        assert_eq!(1.5f64.offset(-2), Some(-0.5));
+    }
+
+    #[test]
+    fn test_numeric_float() {
+        assert_eq!(Numeric::abs(-1.5f64), 1.5);
+        assert_eq!(Numeric::signum(-0.0f32), 0.0);
+        assert_eq!(Numeric::signum(-2.5f64), -1.0);
+        assert_eq!(Numeric::min(1.5f64, -1.0), -1.0);
+        assert_eq!(1.5f32.distance(-1.0), 2.5);
+    }
+
+    #[test]
+    fn test_numeric_conversions() {
+        assert_eq!(u8::from_i64(255), Some(255));
+        assert_eq!(u8::from_i64(-1), None);
+        assert_eq!(f64::from_i64(-3), Some(-3.0));
+        assert_eq!(u64::MAX.to_i64(), None);
+        assert_eq!(3u64.to_i64(), Some(3));
+        assert_eq!((-2.7f64).to_i64(), Some(-2));
+        assert_eq!(f64::NAN.to_i64(), None);
+        assert_eq!(f64::INFINITY.to_i64(), None);
+        assert_eq!(7i16.to_f64(), 7.0);
+    }
+}
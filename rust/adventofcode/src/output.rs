@@ -0,0 +1,156 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::answer::Answer;
+use crate::runner::RunResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Plain,
+    Json,
+    Table,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            "table" => Ok(Self::Table),
+            _ => Err(anyhow!("Unrecognized format: '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct Record {
+    day: u32,
+    part: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    parse_ns: u64,
+    elapsed_ns: u64,
+    input: String,
+}
+
+impl From<&RunResult> for Record {
+    fn from(result: &RunResult) -> Self {
+        let (answer, error) = match &result.answer {
+            Ok(answer) => (Some(answer.clone()), None),
+            Err(e) => (None, Some(format!("{:#}", e))),
+        };
+
+        Record {
+            day: result.day.number(),
+            part: result.part.number(),
+            answer,
+            error,
+            parse_ns: result.parse_elapsed.as_nanos() as u64,
+            elapsed_ns: result.elapsed.as_nanos() as u64,
+            input: result.input.to_string(),
+        }
+    }
+}
+
+fn answer_text(result: &RunResult) -> String {
+    match &result.answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {:#}", e),
+    }
+}
+
+pub fn print_results(format: Format, results: &[RunResult]) -> Result<()> {
+    match format {
+        Format::Plain => print_plain(results),
+        Format::Json => print_json(results)?,
+        Format::Table => print_table(results),
+    }
+
+    Ok(())
+}
+
+fn print_plain(results: &[RunResult]) {
+    if let [result] = results {
+        println!("Solution:\n{}", answer_text(result));
+    } else {
+        for result in results {
+            println!(
+                "{} {}: {}",
+                result.day.name(),
+                result.part,
+                answer_text(result)
+            );
+        }
+    }
+}
+
+fn print_json(results: &[RunResult]) -> Result<()> {
+    let records = results.iter().map(Record::from).collect::<Vec<_>>();
+
+    println!("{}", serde_json::to_string_pretty(&records)?);
+
+    Ok(())
+}
+
+fn print_table(results: &[RunResult]) {
+    println!(
+        "{:<6} {:<6} {:>12} {:>12} Answer",
+        "Day", "Part", "Parse", "Elapsed"
+    );
+
+    for result in results {
+        println!(
+            "{:<6} {:<6} {:>12} {:>12} {}",
+            result.day.name(),
+            result.part,
+            format!("{:.2?}", result.parse_elapsed),
+            format!("{:.2?}", result.elapsed),
+            answer_text(result).replace('\n', &format!("\n{:40}", ""))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::command::Part;
+    use crate::source::InputSource;
+    use crate::Day;
+
+    #[test]
+    fn test_json_record() -> Result<()> {
+        let result = RunResult {
+            day: Day::day02,
+            part: Part::One,
+            input: InputSource::File("inputs/day02.txt".into()),
+            parse_elapsed: Duration::from_micros(5),
+            elapsed: Duration::from_micros(15),
+            answer: Ok(Answer::from(150i64)),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&Record::from(&result))?,
+            r#"{"day":2,"part":1,"answer":150,"parse_ns":5000,"elapsed_ns":15000,"input":"inputs/day02.txt"}"#
+        );
+
+        let result = RunResult {
+            answer: Err(anyhow!("No winning call")),
+            ..result
+        };
+
+        assert_eq!(
+            serde_json::to_string(&Record::from(&result))?,
+            r#"{"day":2,"part":1,"error":"No winning call","parse_ns":5000,"elapsed_ns":15000,"input":"inputs/day02.txt"}"#
+        );
+
+        Ok(())
+    }
+}
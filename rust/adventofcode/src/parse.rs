@@ -0,0 +1,643 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::grid::Grid;
+use crate::source::InputSource;
+use crate::Day;
+
+/// A malformed piece of input, with enough context to show the offending line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<Day>,
+    pub input: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    /// Number of characters to underline, at least 1
+    pub width: usize,
+    pub text: String,
+    pub source_line: String,
+    pub reason: String,
+    /// Byte offset of `text` in the input `line` and `column` count from, and the length
+    /// of that input, so an error from a `FromStr` which parses with its own `Span` can
+    /// be moved to the outer span
+    offset: usize,
+    source_len: usize,
+}
+
+impl ParseError {
+    /// Records which day and input the error came from, the parsers only see the text
+    pub fn located(self, day: Day, input: &InputSource) -> Self {
+        Self {
+            day: Some(day),
+            input: Some(input.to_string()),
+            ..self
+        }
+    }
+
+    /// Renders the error with the source line and the offending text underlined, e.g.
+    ///
+    /// ```text
+    /// error: Invalid value '3x' at line 3, column 1: invalid digit found in string
+    ///  --> day01 inputs/day01.txt:3:1
+    ///   |
+    /// 3 | 3x
+    ///   | ^^
+    /// ```
+    pub fn render(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let origin = match (self.day, &self.input) {
+            (Some(day), Some(input)) => format!("{} {}:", day.name(), input),
+            (Some(day), None) => format!("{} ", day.name()),
+            (None, Some(input)) => format!("{}:", input),
+            (None, None) => String::from("line "),
+        };
+
+        format!(
+            "error: {}\n{}--> {}{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            origin,
+            self.line,
+            self.column,
+            gutter,
+            number,
+            self.source_line,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid value '{}' at line {}, column {}: {}",
+            self.text, self.line, self.column, self.reason
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A piece of the puzzle input which remembers where it came from, so errors can
+/// point at the offending line and column (both 1-based, columns in characters)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    input: &'a str,
+    text: &'a str,
+    /// Byte offset of `text` in `input`
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            text: input,
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    #[allow(dead_code)]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    #[allow(dead_code)]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// `part` must be a slice of this span's text
+    fn subspan(&self, part: &'a str) -> Span<'a> {
+        Locator::new(*self).locate(part)
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.subspan(self.text.trim())
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let mut locator = Locator::new(*self);
+
+        self.text.lines().map(move |line| locator.locate(line))
+    }
+
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+        let mut locator = Locator::new(*self);
+
+        self.text
+            .split(separator)
+            .map(move |part| locator.locate(part))
+    }
+
+    pub fn split_whitespace(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let mut locator = Locator::new(*self);
+
+        self.text
+            .split_whitespace()
+            .map(move |part| locator.locate(part))
+    }
+
+    /// Groups of lines separated by blank lines
+    pub fn blocks(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        self.split("\n\n")
+            .map(|block| block.trim())
+            .filter(|block| !block.is_empty())
+    }
+
+    fn source_line(&self, line: usize) -> String {
+        self.input
+            .lines()
+            .nth(line - 1)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Turns `error` into a `ParseError` at this span, unless it already is one from
+    /// somewhere more specific
+    pub fn error<E>(&self, error: E) -> anyhow::Error
+    where
+        E: Into<anyhow::Error>,
+    {
+        let error = error.into();
+
+        if let Some(inner) = error.downcast_ref::<ParseError>() {
+            if inner.source_len != self.text.len() {
+                return error;
+            }
+
+            // Parsed from a new span over just this text, count from where it starts. A
+            // span over the whole input starts at line 1, column 1, so errors from its own
+            // subspans are unchanged.
+            let line = self.line + inner.line - 1;
+            let column = match inner.line {
+                1 => self.column + inner.column - 1,
+                _ => inner.column,
+            };
+
+            return anyhow::Error::new(ParseError {
+                line,
+                column,
+                source_line: self.source_line(line),
+                offset: self.offset + inner.offset,
+                source_len: self.input.len(),
+                ..inner.clone()
+            });
+        }
+
+        let first_line = self.text.lines().next().unwrap_or_default();
+
+        anyhow::Error::new(ParseError {
+            day: None,
+            input: None,
+            line: self.line,
+            column: self.column,
+            width: first_line.chars().count().max(1),
+            text: self.text.to_string(),
+            source_line: self.source_line(self.line),
+            reason: format!("{:#}", error),
+            offset: self.offset,
+            source_len: self.input.len(),
+        })
+    }
+
+    pub fn parse<T>(&self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        self.text.parse().map_err(|e| self.error(e))
+    }
+
+    pub fn parse_with<T, E, F>(&self, parse: F) -> Result<T>
+    where
+        F: FnOnce(&'a str) -> Result<T, E>,
+        E: Into<anyhow::Error>,
+    {
+        parse(self.text).map_err(|e| self.error(e))
+    }
+
+    /// Splits the span around `literals`, one more than the number of values between
+    /// them, or None if it doesn't fit. Values end at the first occurrence of the
+    /// literal which follows them, except the last which takes the rest of the text.
+    pub fn split_pattern(&self, literals: &[&str]) -> Option<Vec<Span<'a>>> {
+        let (first, rest) = literals.split_first()?;
+        let mut remaining = self.text.strip_prefix(first)?;
+        let mut values = Vec::with_capacity(rest.len());
+        let mut locator = Locator::new(*self);
+
+        for (i, literal) in rest.iter().enumerate() {
+            let (value, after) = if i + 1 == rest.len() {
+                (remaining.strip_suffix(literal)?, "")
+            } else {
+                let index = remaining.find(literal)?;
+                (&remaining[..index], &remaining[index + literal.len()..])
+            };
+
+            values.push(locator.locate(value));
+            remaining = after;
+        }
+
+        remaining.is_empty().then_some(values)
+    }
+}
+
+/// Finds the line and column of slices of a span's text, taken in order, by only
+/// counting the text since the previous one. Counting from the start of the span
+/// each time would make splitting a large input quadratic.
+struct Locator<'a> {
+    span: Span<'a>,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Locator<'a> {
+    fn new(span: Span<'a>) -> Self {
+        Self {
+            span,
+            offset: 0,
+            line: span.line,
+            column: span.column,
+        }
+    }
+
+    /// `part` must be a slice of the span's text starting at or after the previous one
+    fn locate(&mut self, part: &'a str) -> Span<'a> {
+        let offset = part.as_ptr() as usize - self.span.text.as_ptr() as usize;
+        let between = &self.span.text[self.offset..offset];
+
+        match between.rfind('\n') {
+            Some(newline) => {
+                self.line += between.matches('\n').count();
+                self.column = between[newline + 1..].chars().count() + 1;
+            }
+            None => self.column += between.chars().count(),
+        }
+
+        self.offset = offset;
+
+        Span {
+            input: self.span.input,
+            text: part,
+            offset: self.span.offset + offset,
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+impl fmt::Display for Span<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+/// Parsing which keeps the span's location in errors, usually from `#[derive(PuzzleParse)]`
+pub trait FromSpan: Sized {
+    fn from_span(span: Span<'_>) -> Result<Self>;
+}
+
+/// One value per line, ignoring surrounding whitespace
+pub fn lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    Span::new(input)
+        .lines()
+        .map(|line| line.trim().parse())
+        .collect()
+}
+
+/// Values separated by `separator`, ignoring surrounding whitespace
+pub fn separated<T>(input: &str, separator: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    parse_separated(Span::new(input), separator)
+}
+
+pub fn comma_separated<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    separated(input, ",")
+}
+
+pub fn parse_separated<T>(span: Span, separator: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    span.trim()
+        .split(separator)
+        .map(|item| item.trim().parse())
+        .collect()
+}
+
+pub fn blocks(input: &str) -> impl Iterator<Item = Span<'_>> {
+    Span::new(input).blocks()
+}
+
+/// Rows of whitespace separated values, which must all be the same length
+pub fn whitespace_grid<T>(span: Span) -> Result<Grid<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let rows = span
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|cell| cell.parse())
+                .collect::<Result<Vec<T>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Grid::from_rows(rows).map_err(|e| span.error(e))
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+
+    use super::*;
+    use crate::PuzzleParse;
+
+    #[derive(Debug, Default, PartialEq, Eq, PuzzleParse)]
+    #[pattern = "{name} at {position.x},{position.y} {{{size}}}"]
+    struct Record {
+        name: String,
+        position: crate::Point<i64>,
+        size: u8,
+    }
+
+    #[derive(Debug, PartialEq, Eq, PuzzleParse)]
+    enum Instruction {
+        #[pattern = "move {dx},{dy}"]
+        Move { dx: i64, dy: i64 },
+        #[pattern = "turn {0}"]
+        Turn(char),
+        #[pattern = "stop"]
+        Stop,
+    }
+
+    fn message(error: anyhow::Error) -> String {
+        format!("{:#}", error)
+    }
+
+    #[test]
+    fn test_span_locations() {
+        let span = Span::new("ab\n  cd ef\n\ngh");
+
+        assert_eq!(
+            span.lines()
+                .map(|l| (l.as_str(), l.line(), l.column()))
+                .collect::<Vec<_>>(),
+            vec![("ab", 1, 1), ("  cd ef", 2, 1), ("", 3, 1), ("gh", 4, 1)]
+        );
+        assert_eq!(
+            span.split_whitespace()
+                .map(|w| (w.as_str(), w.line(), w.column()))
+                .collect::<Vec<_>>(),
+            vec![("ab", 1, 1), ("cd", 2, 3), ("ef", 2, 6), ("gh", 4, 1)]
+        );
+        assert_eq!(
+            span.blocks()
+                .map(|b| (b.as_str(), b.line(), b.column()))
+                .collect::<Vec<_>>(),
+            vec![("ab\n  cd ef", 1, 1), ("gh", 4, 1)]
+        );
+
+        let line = span.lines().nth(1).unwrap();
+        assert_eq!(line.trim().column(), 3);
+        assert_eq!(line.trim().split(" ").nth(1).unwrap().column(), 6);
+    }
+
+    #[test]
+    fn test_lines() -> Result<()> {
+        assert_eq!(lines::<i64>("1\n -2\n3 \n")?, vec![1, -2, 3]);
+        assert_eq!(
+            message(lines::<i64>("1\n2\n3x\n").unwrap_err()),
+            "Invalid value '3x' at line 3, column 1: invalid digit found in string"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_comma_separated() -> Result<()> {
+        assert_eq!(comma_separated::<u8>("3,4, 3,1,2\n")?, vec![3, 4, 3, 1, 2]);
+        assert_eq!(
+            message(comma_separated::<u8>("3,4,300").unwrap_err()),
+            "Invalid value '300' at line 1, column 5: number too large to fit in target type"
+        );
+        assert_eq!(separated::<u8>("1 | 2", "|")?, vec![1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_whitespace_grid() -> Result<()> {
+        let input = "1,2\n\n 1  2\n30 4\n\n5 6\n7\n";
+        let mut blocks = blocks(input);
+
+        assert_eq!(
+            parse_separated::<u8>(blocks.next().unwrap(), ",")?,
+            vec![1, 2]
+        );
+
+        let grid = whitespace_grid::<u8>(blocks.next().unwrap())?;
+        assert_eq!(grid.to_string(), "12\n304");
+
+        assert_eq!(
+            message(whitespace_grid::<u8>(blocks.next().unwrap()).unwrap_err()),
+            "Invalid value '5 6\n7' at line 6, column 1: Row 1 has 1 cells, expected 2"
+        );
+
+        assert_eq!(
+            message(whitespace_grid::<u8>(Span::new("1 2\n3 x")).unwrap_err()),
+            "Invalid value 'x' at line 2, column 3: invalid digit found in string"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = lines::<i64>("1\n 2 x\n").unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+
+        assert_eq!(
+            error,
+            ParseError {
+                day: None,
+                input: None,
+                line: 2,
+                column: 2,
+                width: 3,
+                text: String::from("2 x"),
+                source_line: String::from(" 2 x"),
+                reason: String::from("invalid digit found in string"),
+                offset: 3,
+                source_len: 7,
+            }
+        );
+        assert_eq!(
+            error.render(),
+            "error: Invalid value '2 x' at line 2, column 2: invalid digit found in string
+ --> line 2:2
+  |
+2 |  2 x
+  |  ^^^"
+        );
+
+        let error = error.located(Day::day01, &InputSource::File("inputs/bad.txt".into()));
+        assert_eq!(
+            error.render().lines().nth(1),
+            Some(" --> day01 inputs/bad.txt:2:2")
+        );
+    }
+
+    #[test]
+    fn test_parse_error_nested() {
+        let outer = Span::new("a\nb c");
+        let inner = outer
+            .lines()
+            .nth(1)
+            .unwrap()
+            .split_whitespace()
+            .nth(1)
+            .unwrap();
+
+        let error = outer.error(inner.error(anyhow!("Bad")));
+        let error = error.downcast::<ParseError>().unwrap();
+
+        assert_eq!((error.line, error.column, error.width), (2, 3, 1));
+        assert_eq!(error.source_line, "b c");
+
+        let error = outer.error(anyhow!("Bad"));
+        let error = error.downcast::<ParseError>().unwrap();
+
+        assert_eq!(
+            (error.line, error.width, error.text.as_str()),
+            (1, 1, "a\nb c")
+        );
+
+        // Errors from other text are left alone, even when it starts at the same address
+        let text = "ab\nc";
+        let unrelated = Span::new(text)
+            .lines()
+            .nth(1)
+            .unwrap()
+            .error(anyhow!("Bad"));
+        let first = Span::new(&text[..2]);
+        let error = first.error(unrelated).downcast::<ParseError>().unwrap();
+
+        assert_eq!((error.line, error.column, error.offset), (2, 1, 3));
+        assert_eq!(error.source_line, "c");
+    }
+
+    #[test]
+    fn test_large_input() -> Result<()> {
+        let input = (0..100_000).map(|n| format!("{}\n", n)).collect::<String>();
+
+        assert_eq!(lines::<u32>(&input)?.len(), 100_000);
+        assert_eq!(
+            comma_separated::<u32>(input.trim().replace('\n', ",").as_str())?.len(),
+            100_000
+        );
+
+        let error = lines::<u32>(&format!("{}x", input)).unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+
+        assert_eq!((error.line, error.column), (100_001, 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_pattern() {
+        let span = Span::new("x\n1,2 -> 3,4");
+        let line = span.lines().nth(1).unwrap();
+
+        assert_eq!(
+            line.split_pattern(&["", ",", " -> ", ",", ""])
+                .unwrap()
+                .iter()
+                .map(|s| (s.as_str(), s.column()))
+                .collect::<Vec<_>>(),
+            vec![("1", 1), ("2", 3), ("3", 8), ("4", 10)]
+        );
+        assert_eq!(line.split_pattern(&["", " => ", ""]), None);
+        assert_eq!(line.split_pattern(&["1,2 -> 3,4"]), Some(vec![]));
+        assert_eq!(line.split_pattern(&["1,2"]), None);
+        assert_eq!(
+            Span::new("a, b, c")
+                .split_pattern(&["", ", ", ""])
+                .unwrap()
+                .iter()
+                .map(Span::as_str)
+                .collect::<Vec<_>>(),
+            vec!["a", "b, c"]
+        );
+    }
+
+    #[test]
+    fn test_derive() -> Result<()> {
+        assert_eq!(
+            "sub at -3,4 {9}".parse::<Record>()?,
+            Record {
+                name: String::from("sub"),
+                position: crate::Point::new(-3, 4),
+                size: 9,
+            }
+        );
+        assert_eq!(
+            lines::<Instruction>("move 1,-2\nturn L\nstop")?,
+            vec![
+                Instruction::Move { dx: 1, dy: -2 },
+                Instruction::Turn('L'),
+                Instruction::Stop
+            ]
+        );
+
+        assert_eq!(
+            message("sub at 3,4".parse::<Record>().unwrap_err()),
+            "Invalid value 'sub at 3,4' at line 1, column 1: Expected '{name} at {position.x},{position.y} {{{size}}}'"
+        );
+        assert_eq!(
+            message(lines::<Instruction>("stop\nmove 1,x").unwrap_err()),
+            "Invalid value 'x' at line 2, column 8: invalid digit found in string"
+        );
+        assert_eq!(
+            message(lines::<Instruction>("jump").unwrap_err()),
+            "Invalid value 'jump' at line 1, column 1: Expected one of 'move {dx},{dy}', 'turn {0}', 'stop'"
+        );
+
+        Ok(())
+    }
+}
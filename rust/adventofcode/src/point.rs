@@ -1,5 +1,13 @@
+use std::array;
+use std::convert::TryFrom;
+use std::num::TryFromIntError;
 use std::ops;
 
+use anyhow::{anyhow, Result};
+
+use crate::numeric::{CheckedArithmetic, Numeric};
+
+/// The dimensions of the XY plane, which every `Point` has
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Dimension {
     X,
@@ -15,27 +23,64 @@ impl Dimension {
     }
 }
 
+/// The dimensions of `Point3`, and the first three of `PointN`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    #[allow(dead_code)]
+    pub const ALL: [Axis; 3] = [Self::X, Self::Y, Self::Z];
+
+    pub fn index(&self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::Y => 1,
+            Self::Z => 2,
+        }
+    }
+}
+
+impl From<Dimension> for Axis {
+    fn from(dimension: Dimension) -> Self {
+        match dimension {
+            Dimension::X => Self::X,
+            Dimension::Y => Self::Y,
+        }
+    }
+}
+
+/// Points which can be read and written by every `D`
+pub trait Dimensioned<T, D = Dimension> {
+    fn get(&self, dimension: D) -> T;
+    fn set(&mut self, dimension: D, value: T);
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct DimensionedValue<T> {
-    dimension: Dimension,
+pub struct DimensionedValue<T, D = Dimension> {
+    dimension: D,
     value: T,
 }
 
-impl<T> DimensionedValue<T> {
-    pub fn new(dimension: Dimension, value: T) -> Self {
+impl<T, D> DimensionedValue<T, D> {
+    pub fn new(dimension: D, value: T) -> Self {
         Self { dimension, value }
     }
 }
 
-impl<T> DimensionedValue<T>
+impl<T, D> DimensionedValue<T, D>
 where
     T: Copy,
+    D: Copy,
 {
-    pub fn apply(&self, point: &mut Point<T>) {
-        match self.dimension {
-            Dimension::X => point.x = self.value,
-            Dimension::Y => point.y = self.value,
-        }
+    pub fn apply<P>(&self, point: &mut P)
+    where
+        P: Dimensioned<T, D>,
+    {
+        point.set(self.dimension, self.value)
     }
 }
 
@@ -45,19 +90,102 @@ pub struct Point<T> {
     pub y: T,
 }
 
-pub trait Absolute {
-    fn absolute(&self) -> Self;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl Absolute for i64 {
-    fn absolute(&self) -> Self {
-        self.abs()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointN<T, const N: usize>(pub [T; N]);
+
+/// Compass directions with y increasing southwards, the same way rows are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    pub const ALL: [Direction; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    pub fn unit(&self) -> Point<i64> {
+        match self {
+            Self::North => Point::new(0, -1),
+            Self::NorthEast => Point::new(1, -1),
+            Self::East => Point::new(1, 0),
+            Self::SouthEast => Point::new(1, 1),
+            Self::South => Point::new(0, 1),
+            Self::SouthWest => Point::new(-1, 1),
+            Self::West => Point::new(-1, 0),
+            Self::NorthWest => Point::new(-1, -1),
+        }
     }
 }
 
-impl Absolute for u64 {
-    fn absolute(&self) -> Self {
-        *self
+/// An inclusive rectangle from `min` to `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T> Bounds<T>
+where
+    T: PartialOrd,
+{
+    pub fn new(min: Point<T>, max: Point<T>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, point: &Point<T>) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+    }
+}
+
+impl<T> Bounds<T>
+where
+    T: PartialOrd + Copy,
+{
+    pub fn around(point: Point<T>) -> Self {
+        Self::new(point, point)
+    }
+
+    /// Grows the bounds just enough to contain `point`
+    pub fn include(&mut self, point: &Point<T>) {
+        if point.x < self.min.x {
+            self.min.x = point.x;
+        }
+        if point.y < self.min.y {
+            self.min.y = point.y;
+        }
+        if point.x > self.max.x {
+            self.max.x = point.x;
+        }
+        if point.y > self.max.y {
+            self.max.y = point.y;
+        }
     }
 }
 
@@ -67,13 +195,137 @@ impl<T> Point<T> {
     }
 }
 
+#[allow(dead_code)]
 impl<T> Point<T>
 where
-    T: Absolute,
+    T: Numeric,
 {
-    #[allow(dead_code)]
     pub fn abs(&self) -> Self {
-        Self::new(self.x.absolute(), self.y.absolute())
+        Self::new(self.x.abs(), self.y.abs())
+    }
+
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Point<T>
+where
+    T: Numeric,
+{
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// None if moving by `delta` would leave the range of `T`
+    pub fn offset(&self, delta: Point<i64>) -> Option<Self> {
+        Some(Self::new(self.x.offset(delta.x)?, self.y.offset(delta.y)?))
+    }
+
+    pub fn step(&self, direction: Direction) -> Option<Self> {
+        self.offset(direction.unit())
+    }
+
+    pub fn neighbors4(&self, bounds: Option<Bounds<T>>) -> impl Iterator<Item = Self> {
+        self.neighbors(&Direction::CARDINAL, bounds)
+    }
+
+    pub fn neighbors8(&self, bounds: Option<Bounds<T>>) -> impl Iterator<Item = Self> {
+        self.neighbors(&Direction::ALL, bounds)
+    }
+
+    fn neighbors(
+        &self,
+        directions: &'static [Direction],
+        bounds: Option<Bounds<T>>,
+    ) -> impl Iterator<Item = Self> {
+        let point = *self;
+
+        directions
+            .iter()
+            .filter_map(move |direction| point.step(*direction))
+            .filter(move |neighbor| bounds.is_none_or(|bounds| bounds.contains(neighbor)))
+    }
+}
+
+/// Component-wise versions of the primitive integer methods, `checked_*` is None if
+/// either component overflows
+#[allow(dead_code)]
+impl<T> Point<T>
+where
+    T: CheckedArithmetic + Copy,
+{
+    pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add(rhs.x)?,
+            self.y.checked_add(rhs.y)?,
+        ))
+    }
+
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_sub(rhs.x)?,
+            self.y.checked_sub(rhs.y)?,
+        ))
+    }
+
+    pub fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_mul(rhs.x)?,
+            self.y.checked_mul(rhs.y)?,
+        ))
+    }
+
+    pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_div(rhs.x)?,
+            self.y.checked_div(rhs.y)?,
+        ))
+    }
+
+    pub fn saturating_add(&self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_add(rhs.x), self.y.saturating_add(rhs.y))
+    }
+
+    pub fn saturating_sub(&self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_sub(rhs.x), self.y.saturating_sub(rhs.y))
+    }
+
+    pub fn saturating_mul(&self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_mul(rhs.x), self.y.saturating_mul(rhs.y))
+    }
+
+    pub fn wrapping_add(&self, rhs: Self) -> Self {
+        Self::new(self.x.wrapping_add(rhs.x), self.y.wrapping_add(rhs.y))
+    }
+
+    pub fn wrapping_sub(&self, rhs: Self) -> Self {
+        Self::new(self.x.wrapping_sub(rhs.x), self.y.wrapping_sub(rhs.y))
+    }
+
+    pub fn wrapping_mul(&self, rhs: Self) -> Self {
+        Self::new(self.x.wrapping_mul(rhs.x), self.y.wrapping_mul(rhs.y))
+    }
+}
+
+impl TryFrom<Point<i64>> for Point<u64> {
+    type Error = TryFromIntError;
+
+    fn try_from(p: Point<i64>) -> Result<Self, Self::Error> {
+        Ok(Self::new(u64::try_from(p.x)?, u64::try_from(p.y)?))
+    }
+}
+
+impl TryFrom<Point<u64>> for Point<i64> {
+    type Error = TryFromIntError;
+
+    fn try_from(p: Point<u64>) -> Result<Self, Self::Error> {
+        Ok(Self::new(i64::try_from(p.x)?, i64::try_from(p.y)?))
     }
 }
 
@@ -91,17 +343,23 @@ where
     }
 }
 
-impl<T> Point<T>
+impl<T> Dimensioned<T> for Point<T>
 where
     T: Copy,
 {
-    #[allow(dead_code)]
-    pub fn get(&self, dimension: Dimension) -> T {
+    fn get(&self, dimension: Dimension) -> T {
         match dimension {
             Dimension::X => self.x,
             Dimension::Y => self.y,
         }
     }
+
+    fn set(&mut self, dimension: Dimension, value: T) {
+        match dimension {
+            Dimension::X => self.x = value,
+            Dimension::Y => self.y = value,
+        }
+    }
 }
 
 impl<T> Point<T>
@@ -114,174 +372,343 @@ where
     }
 }
 
-impl<T> ops::Add<Point<T>> for Point<T>
+/// Quarter turns are as seen on screen, with y increasing downwards
+#[allow(dead_code)]
+impl<T> Point<T>
 where
-    T: ops::Add<T>,
+    T: ops::Neg<Output = T> + Copy,
 {
-    type Output = Point<<T as ops::Add<T>>::Output>;
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self::Output::new(self.x + rhs.x, self.y + rhs.y)
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::new(self.y, -self.x)
     }
-}
 
-impl<T> ops::Add<T> for Point<T>
-where
-    T: ops::Add<T> + Copy,
-{
-    type Output = Point<<T as ops::Add<T>>::Output>;
+    pub fn rotate_180(&self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
 
-    fn add(self, rhs: T) -> Self::Output {
-        Self::Output::new(self.x + rhs, self.y + rhs)
+    /// Negates the `dimension` component, mirroring across the other axis
+    pub fn reflect(&self, dimension: Dimension) -> Self {
+        match dimension {
+            Dimension::X => Self::new(-self.x, self.y),
+            Dimension::Y => Self::new(self.x, -self.y),
+        }
     }
 }
 
-impl<T> ops::AddAssign<Point<T>> for Point<T>
-where
-    T: ops::AddAssign<T>,
-{
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
     }
 }
 
-impl<T> ops::AddAssign<T> for Point<T>
+#[allow(dead_code)]
+impl<T> Point3<T>
 where
-    T: ops::AddAssign<T> + Copy,
+    T: Numeric,
 {
-    fn add_assign(&mut self, rhs: T) {
-        self.x += rhs;
-        self.y += rhs;
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs(), self.z.abs())
     }
-}
 
-impl<T> ops::Sub<Point<T>> for Point<T>
-where
-    T: ops::Sub<T>,
-{
-    type Output = Point<<T as ops::Sub<T>>::Output>;
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self::Output::new(self.x - rhs.x, self.y - rhs.y)
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
     }
 }
 
-impl<T> ops::Sub<T> for Point<T>
+#[allow(dead_code)]
+impl<T> Point3<T>
 where
-    T: ops::Sub<T> + Copy,
+    T: Default + Copy,
 {
-    type Output = Point<<T as ops::Sub<T>>::Output>;
+    pub fn from_dimensioned_values(
+        d1: DimensionedValue<T, Axis>,
+        d2: DimensionedValue<T, Axis>,
+        d3: DimensionedValue<T, Axis>,
+    ) -> Self {
+        let mut p = Default::default();
 
-    fn sub(self, rhs: T) -> Self::Output {
-        Self::Output::new(self.x - rhs, self.y - rhs)
+        d1.apply(&mut p);
+        d2.apply(&mut p);
+        d3.apply(&mut p);
+
+        p
     }
 }
 
-impl<T> ops::SubAssign<Point<T>> for Point<T>
+impl<T> Dimensioned<T, Axis> for Point3<T>
 where
-    T: ops::SubAssign<T>,
+    T: Copy,
 {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
+    fn get(&self, axis: Axis) -> T {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+
+    fn set(&mut self, axis: Axis, value: T) {
+        match axis {
+            Axis::X => self.x = value,
+            Axis::Y => self.y = value,
+            Axis::Z => self.z = value,
+        }
     }
 }
 
-impl<T> ops::SubAssign<T> for Point<T>
+#[allow(dead_code)]
+impl<T> Point3<T>
 where
-    T: ops::SubAssign<T> + Copy,
+    T: Copy,
 {
-    fn sub_assign(&mut self, rhs: T) {
-        self.x -= rhs;
-        self.y -= rhs;
+    pub fn as_tuple(&self) -> (T, T, T) {
+        (self.x, self.y, self.z)
     }
 }
 
-impl<T> ops::Mul<Point<T>> for Point<T>
+/// Quarter turns follow the right-hand rule around each axis
+#[allow(dead_code)]
+impl<T> Point3<T>
 where
-    T: ops::Mul<T>,
+    T: ops::Neg<Output = T> + Copy,
 {
-    type Output = Point<<T as ops::Mul<T>>::Output>;
+    pub fn rotate_x(&self) -> Self {
+        Self::new(self.x, -self.z, self.y)
+    }
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        Self::Output::new(self.x * rhs.x, self.y * rhs.y)
+    pub fn rotate_y(&self) -> Self {
+        Self::new(self.z, self.y, -self.x)
+    }
+
+    pub fn rotate_z(&self) -> Self {
+        Self::new(-self.y, self.x, self.z)
+    }
+
+    pub fn reflect(&self, axis: Axis) -> Self {
+        match axis {
+            Axis::X => Self::new(-self.x, self.y, self.z),
+            Axis::Y => Self::new(self.x, -self.y, self.z),
+            Axis::Z => Self::new(self.x, self.y, -self.z),
+        }
+    }
+}
+
+impl<T, const N: usize> PointN<T, N> {
+    pub fn new(coords: [T; N]) -> Self {
+        PointN(coords)
     }
 }
 
-impl<T> ops::Mul<T> for Point<T>
+#[allow(dead_code)]
+impl<T, const N: usize> PointN<T, N>
 where
-    T: ops::Mul<T> + Copy,
+    T: Numeric,
 {
-    type Output = Point<<T as ops::Mul<T>>::Output>;
+    pub fn abs(&self) -> Self {
+        Self::new(array::from_fn(|i| self.0[i].abs()))
+    }
 
-    fn mul(self, rhs: T) -> Self::Output {
-        Self::Output::new(self.x * rhs, self.y * rhs)
+    pub fn manhattan(&self, other: &Self) -> T {
+        (0..N).fold(T::zero(), |sum, i| sum + self.0[i].distance(other.0[i]))
     }
 }
 
-impl<T> ops::MulAssign<Point<T>> for Point<T>
+#[allow(dead_code)]
+impl<T, const N: usize> PointN<T, N>
 where
-    T: ops::MulAssign<T>,
+    T: Default + Copy,
 {
-    fn mul_assign(&mut self, rhs: Self) {
-        self.x *= rhs.x;
-        self.y *= rhs.y;
+    /// Only the first three coordinates can be named by an `Axis`, use indexing for
+    /// the rest. Errors if a value's axis is beyond `N`.
+    pub fn from_dimensioned_values<I>(values: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = DimensionedValue<T, Axis>>,
+    {
+        let mut p = Self::default();
+
+        for value in values {
+            p.set(value.dimension, value.value)?;
+        }
+
+        Ok(p)
     }
 }
 
-impl<T> ops::MulAssign<T> for Point<T>
+impl<T, const N: usize> Default for PointN<T, N>
 where
-    T: ops::MulAssign<T> + Copy,
+    T: Default,
 {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| T::default()))
     }
 }
 
-impl<T> ops::Div<Point<T>> for Point<T>
+/// Unlike `Point` and `Point3` these can fail, when `N` is too small to have the axis
+#[allow(dead_code)]
+impl<T, const N: usize> PointN<T, N>
 where
-    T: ops::Div<T>,
+    T: Copy,
 {
-    type Output = Point<<T as ops::Div<T>>::Output>;
+    pub fn get(&self, axis: Axis) -> Option<T> {
+        self.0.get(axis.index()).copied()
+    }
+
+    pub fn set(&mut self, axis: Axis, value: T) -> Result<()> {
+        let coordinate = self
+            .0
+            .get_mut(axis.index())
+            .ok_or_else(|| anyhow!("A {} dimensional point has no {:?} axis", N, axis))?;
 
-    fn div(self, rhs: Self) -> Self::Output {
-        Self::Output::new(self.x / rhs.x, self.y / rhs.y)
+        *coordinate = value;
+
+        Ok(())
     }
 }
 
-impl<T> ops::Div<T> for Point<T>
-where
-    T: ops::Div<T> + Copy,
-{
-    type Output = Point<<T as ops::Div<T>>::Output>;
+impl<T, const N: usize> ops::Index<usize> for PointN<T, N> {
+    type Output = T;
 
-    fn div(self, rhs: T) -> Self::Output {
-        Self::Output::new(self.x / rhs, self.y / rhs)
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
     }
 }
 
-impl<T> ops::DivAssign<Point<T>> for Point<T>
-where
-    T: ops::DivAssign<T>,
-{
-    fn div_assign(&mut self, rhs: Self) {
-        self.x /= rhs.x;
-        self.y /= rhs.y;
+impl<T, const N: usize> ops::IndexMut<usize> for PointN<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
     }
 }
 
-impl<T> ops::DivAssign<T> for Point<T>
-where
-    T: ops::DivAssign<T> + Copy,
-{
-    fn div_assign(&mut self, rhs: T) {
-        self.x /= rhs;
-        self.y /= rhs;
+impl<T> From<Point<T>> for PointN<T, 2> {
+    fn from(p: Point<T>) -> Self {
+        PointN([p.x, p.y])
+    }
+}
+
+impl<T> From<Point3<T>> for PointN<T, 3> {
+    fn from(p: Point3<T>) -> Self {
+        PointN([p.x, p.y, p.z])
     }
 }
 
+// Component-wise operators against another point of the same type, and against a scalar
+// which is applied to every component.
+macro_rules! impl_point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl_point_ops!(@op $point { $($field),+ } Add add AddAssign add_assign);
+        impl_point_ops!(@op $point { $($field),+ } Sub sub SubAssign sub_assign);
+        impl_point_ops!(@op $point { $($field),+ } Mul mul MulAssign mul_assign);
+        impl_point_ops!(@op $point { $($field),+ } Div div DivAssign div_assign);
+    };
+
+    (PointN) => {
+        impl_point_ops!(@op PointN Add add AddAssign add_assign);
+        impl_point_ops!(@op PointN Sub sub SubAssign sub_assign);
+        impl_point_ops!(@op PointN Mul mul MulAssign mul_assign);
+        impl_point_ops!(@op PointN Div div DivAssign div_assign);
+    };
+
+    (@op $point:ident { $($field:ident),+ } $op:ident $method:ident $assign:ident $assign_method:ident) => {
+        impl<T> ops::$op<$point<T>> for $point<T>
+        where
+            T: ops::$op<T>,
+        {
+            type Output = $point<<T as ops::$op<T>>::Output>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                $point { $($field: ops::$op::$method(self.$field, rhs.$field)),+ }
+            }
+        }
+
+        impl<T> ops::$op<T> for $point<T>
+        where
+            T: ops::$op<T> + Copy,
+        {
+            type Output = $point<<T as ops::$op<T>>::Output>;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                $point { $($field: ops::$op::$method(self.$field, rhs)),+ }
+            }
+        }
+
+        impl<T> ops::$assign<$point<T>> for $point<T>
+        where
+            T: ops::$assign<T>,
+        {
+            fn $assign_method(&mut self, rhs: Self) {
+                $(ops::$assign::$assign_method(&mut self.$field, rhs.$field);)+
+            }
+        }
+
+        impl<T> ops::$assign<T> for $point<T>
+        where
+            T: ops::$assign<T> + Copy,
+        {
+            fn $assign_method(&mut self, rhs: T) {
+                $(ops::$assign::$assign_method(&mut self.$field, rhs);)+
+            }
+        }
+    };
+
+    (@op PointN $op:ident $method:ident $assign:ident $assign_method:ident) => {
+        impl<T, const N: usize> ops::$op<PointN<T, N>> for PointN<T, N>
+        where
+            T: ops::$op<T> + Copy,
+        {
+            type Output = PointN<<T as ops::$op<T>>::Output, N>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                PointN(array::from_fn(|i| ops::$op::$method(self.0[i], rhs.0[i])))
+            }
+        }
+
+        impl<T, const N: usize> ops::$op<T> for PointN<T, N>
+        where
+            T: ops::$op<T> + Copy,
+        {
+            type Output = PointN<<T as ops::$op<T>>::Output, N>;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                PointN(array::from_fn(|i| ops::$op::$method(self.0[i], rhs)))
+            }
+        }
+
+        impl<T, const N: usize> ops::$assign<PointN<T, N>> for PointN<T, N>
+        where
+            T: ops::$assign<T>,
+        {
+            fn $assign_method(&mut self, rhs: Self) {
+                for (lhs, rhs) in self.0.iter_mut().zip(rhs.0) {
+                    ops::$assign::$assign_method(lhs, rhs);
+                }
+            }
+        }
+
+        impl<T, const N: usize> ops::$assign<T> for PointN<T, N>
+        where
+            T: ops::$assign<T> + Copy,
+        {
+            fn $assign_method(&mut self, rhs: T) {
+                for lhs in self.0.iter_mut() {
+                    ops::$assign::$assign_method(lhs, rhs);
+                }
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point { x, y });
+impl_point_ops!(Point3 { x, y, z });
+impl_point_ops!(PointN);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -347,4 +774,250 @@ mod test {
         p /= 2;
         assert_eq!(p, Point::new(5, 3));
     }
+
+    #[test]
+    fn test_point_dimensions() {
+        let p = Point::from_dimensioned_values(
+            DimensionedValue::new(Dimension::Y, 2),
+            DimensionedValue::new(Dimension::X, 1),
+        );
+        assert_eq!(p, Point::new(1, 2));
+        assert_eq!(p.get(Dimension::X), 1);
+        assert_eq!(p.get(Dimension::X.other()), 2);
+    }
+
+    #[test]
+    fn test_missing_axis() {
+        let mut p = PointN::new([1, 2]);
+
+        assert_eq!(p.get(Axis::Y), Some(2));
+        assert_eq!(p.get(Axis::Z), None);
+        assert_eq!(
+            p.set(Axis::Z, 3).unwrap_err().to_string(),
+            "A 2 dimensional point has no Z axis"
+        );
+        assert_eq!(p, PointN::new([1, 2]));
+        assert!(
+            PointN::<i64, 2>::from_dimensioned_values(vec![DimensionedValue::new(Axis::Z, 3)])
+                .is_err()
+        );
+        assert_eq!(
+            PointN::<i64, 4>::from_dimensioned_values(vec![DimensionedValue::new(Axis::Z, 3)])
+                .unwrap(),
+            PointN::new([0, 0, 3, 0])
+        );
+    }
+
+    #[test]
+    fn test_point3() {
+        assert_eq!(Point3::new(1, 2, 3).as_tuple(), (1, 2, 3));
+        assert_eq!(Point3::new(-1i64, 2, -3).abs(), Point3::new(1, 2, 3));
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(4, 5, 6),
+            Point3::new(5, 7, 9)
+        );
+        assert_eq!(Point3::new(4, 5, 6) - 1, Point3::new(3, 4, 5));
+        assert_eq!(Point3::new(1, 2, 3) * 2, Point3::new(2, 4, 6));
+        assert_eq!(
+            Point3::new(4, 6, 8) / Point3::new(2, 3, 4),
+            Point3::new(2, 2, 2)
+        );
+
+        let mut p = Point3::new(1, 2, 3);
+        p += Point3::new(1, 1, 1);
+        p *= 3;
+        assert_eq!(p, Point3::new(6, 9, 12));
+
+        let p = Point3::from_dimensioned_values(
+            DimensionedValue::new(Axis::Z, 3),
+            DimensionedValue::new(Axis::X, 1),
+            DimensionedValue::new(Axis::from(Dimension::Y), 2),
+        );
+        assert_eq!(p, Point3::new(1, 2, 3));
+        assert_eq!(Axis::ALL.map(|axis| p.get(axis)), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_point_n() {
+        let p = PointN::new([1i64, -2, 3, -4]);
+
+        assert_eq!(p[3], -4);
+        assert_eq!(p.get(Axis::Y), Some(-2));
+        assert_eq!(p.abs(), PointN::new([1, 2, 3, 4]));
+        assert_eq!(p + PointN::new([1, 1, 1, 1]), PointN::new([2, -1, 4, -3]));
+        assert_eq!(p - 1, PointN::new([0, -3, 2, -5]));
+        assert_eq!(p * p, PointN::new([1, 4, 9, 16]));
+        assert_eq!(p / -1, PointN::new([-1, 2, -3, 4]));
+
+        let mut q = p;
+        q -= p;
+        assert_eq!(q, PointN::default());
+        q += 5;
+        q /= PointN::new([5, 1, 5, 1]);
+        assert_eq!(q, PointN::new([1, 5, 1, 5]));
+
+        assert_eq!(
+            PointN::<i64, 3>::from_dimensioned_values(vec![
+                DimensionedValue::new(Axis::Z, 3),
+                DimensionedValue::new(Axis::X, 1),
+            ])
+            .unwrap(),
+            PointN::new([1, 0, 3])
+        );
+        assert_eq!(PointN::from(Point::new(1, 2)), PointN::new([1, 2]));
+        assert_eq!(PointN::from(Point3::new(1, 2, 3)), PointN::new([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_point_distance() {
+        assert_eq!(Point::new(1i64, 2).manhattan(&Point::new(-3, 5)), 7);
+        assert_eq!(Point::new(1i64, 2).chebyshev(&Point::new(-3, 5)), 4);
+        assert_eq!(Point::new(5u64, 2).manhattan(&Point::new(3, 7)), 7);
+        assert_eq!(Point::new(5u64, 2).chebyshev(&Point::new(3, 7)), 5);
+        assert_eq!(Point::new(3u64, 3).manhattan(&Point::new(3, 3)), 0);
+    }
+
+    #[test]
+    fn test_point_direction() {
+        assert_eq!(
+            Direction::ALL
+                .iter()
+                .fold(Point::default(), |p, d| p + d.unit()),
+            Point::new(0, 0)
+        );
+        assert!(Direction::ALL
+            .iter()
+            .all(|d| d.unit().chebyshev(&Point::default()) == 1));
+        assert_eq!(
+            Point::new(2u64, 0).step(Direction::SouthWest),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(Point::new(2u64, 0).step(Direction::North), None);
+        assert_eq!(Point::new(i64::MAX, 0).step(Direction::East), None);
+    }
+
+    #[test]
+    fn test_point_neighbors() {
+        assert_eq!(
+            Point::new(1i64, 1).neighbors4(None).collect::<Vec<_>>(),
+            vec![
+                Point::new(1, 0),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(0, 1)
+            ]
+        );
+        assert_eq!(Point::new(1i64, 1).neighbors8(None).count(), 8);
+
+        assert_eq!(
+            Point::new(0u64, 0).neighbors8(None).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(1, 1), Point::new(0, 1)]
+        );
+
+        let bounds = Bounds::new(Point::new(0i64, 0), Point::new(4, 2));
+        assert_eq!(
+            Point::new(4i64, 2)
+                .neighbors8(Some(bounds))
+                .collect::<Vec<_>>(),
+            vec![Point::new(4, 1), Point::new(3, 2), Point::new(3, 1)]
+        );
+        assert_eq!(Point::new(2i64, 1).neighbors4(Some(bounds)).count(), 4);
+    }
+
+    #[test]
+    fn test_point_rotate() {
+        let p = Point::new(3, 1);
+
+        assert_eq!(p.rotate_clockwise(), Point::new(-1, 3));
+        assert_eq!(p.rotate_counterclockwise(), Point::new(1, -3));
+        assert_eq!(p.rotate_180(), Point::new(-3, -1));
+        assert_eq!(p.rotate_clockwise().rotate_clockwise(), p.rotate_180());
+        assert_eq!(p.reflect(Dimension::X), Point::new(-3, 1));
+        assert_eq!(p.reflect(Dimension::Y), Point::new(3, -1));
+        assert_eq!(
+            Direction::North.unit().rotate_clockwise(),
+            Direction::East.unit()
+        );
+
+        let p = Point3::new(1, 2, 3);
+
+        assert_eq!(p.rotate_x(), Point3::new(1, -3, 2));
+        assert_eq!(p.rotate_y(), Point3::new(3, 2, -1));
+        assert_eq!(p.rotate_z(), Point3::new(-2, 1, 3));
+        assert_eq!(p.rotate_x().rotate_x().rotate_x().rotate_x(), p);
+        assert_eq!(p.reflect(Axis::Z), Point3::new(1, 2, -3));
+    }
+
+    #[test]
+    fn test_point_checked() {
+        let p = Point::new(5u64, 2);
+
+        assert_eq!(p.checked_add(Point::new(1, 1)), Some(Point::new(6, 3)));
+        assert_eq!(p.checked_sub(Point::new(3, 2)), Some(Point::new(2, 0)));
+        assert_eq!(p.checked_sub(Point::new(3, 3)), None);
+        assert_eq!(p.checked_mul(Point::new(u64::MAX, 0)), None);
+        assert_eq!(p.checked_div(Point::new(2, 2)), Some(Point::new(2, 1)));
+        assert_eq!(p.checked_div(Point::new(1, 0)), None);
+        assert_eq!(Point::new(i64::MAX, 0).checked_add(Point::new(1, 0)), None);
+    }
+
+    #[test]
+    fn test_point_saturating_wrapping() {
+        let p = Point::new(5u64, 2);
+
+        assert_eq!(p.saturating_sub(Point::new(3, 3)), Point::new(2, 0));
+        assert_eq!(
+            p.saturating_add(Point::new(u64::MAX, 1)),
+            Point::new(u64::MAX, 3)
+        );
+        assert_eq!(
+            Point::new(i64::MIN, 2).saturating_mul(Point::new(2, 2)),
+            Point::new(i64::MIN, 4)
+        );
+        assert_eq!(p.wrapping_sub(Point::new(6, 2)), Point::new(u64::MAX, 0));
+        assert_eq!(
+            Point::new(u8::MAX, 1).wrapping_add(Point::new(1, 1)),
+            Point::new(0, 2)
+        );
+        assert_eq!(
+            Point::new(128u8, 3).wrapping_mul(Point::new(2, 2)),
+            Point::new(0, 6)
+        );
+    }
+
+    #[test]
+    fn test_point_try_from() {
+        assert_eq!(
+            Point::<u64>::try_from(Point::new(3i64, 4)),
+            Ok(Point::new(3u64, 4))
+        );
+        assert!(Point::<u64>::try_from(Point::new(3i64, -4)).is_err());
+        assert_eq!(
+            Point::<i64>::try_from(Point::new(3u64, 4)),
+            Ok(Point::new(3i64, 4))
+        );
+        assert!(Point::<i64>::try_from(Point::new(u64::MAX, 4)).is_err());
+    }
+
+    #[test]
+    fn test_point_numeric() {
+        assert_eq!(Point::new(-3i32, 4).abs(), Point::new(3, 4));
+        assert_eq!(Point::new(-3i32, 0).signum(), Point::new(-1, 0));
+        assert_eq!(Point::new(1usize, 7).manhattan(&Point::new(4, 2)), 8);
+        assert_eq!(Point::new(1.5f64, -2.0).abs(), Point::new(1.5, 2.0));
+        assert_eq!(
+            Point::new(0.5f64, 1.0).chebyshev(&Point::new(2.0, 0.0)),
+            1.5
+        );
+        assert_eq!(
+            Point::new(0.5f64, 1.0).step(Direction::NorthWest),
+            Some(Point::new(-0.5, 0.0))
+        );
+        assert_eq!(Point::new(0u8, 0).neighbors8(None).count(), 3);
+        assert_eq!(Point3::new(1i32, -2, 3).manhattan(&Point3::new(0, 0, 0)), 6);
+        assert_eq!(
+            PointN::new([1i16, -2, 3, -4]).manhattan(&PointN::default()),
+            10
+        );
+    }
 }
@@ -0,0 +1,230 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+
+use crate::answer::Answer;
+use crate::command::Part;
+use crate::output::{self, Format};
+use crate::parse::ParseError;
+use crate::solution::Run;
+use crate::source::InputSource;
+use crate::{Day, Solutions};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRange(RangeInclusive<u32>);
+
+impl DayRange {
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day.number())
+    }
+}
+
+impl Default for DayRange {
+    fn default() -> Self {
+        DayRange(1..=25)
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_bound = |bound: &str, default: u32| -> Result<u32> {
+            if bound.is_empty() {
+                Ok(default)
+            } else {
+                bound
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid day: '{}'", bound))
+            }
+        };
+
+        let range = if let Some((start, end)) = s.split_once("..=") {
+            parse_bound(start, 1)?..=parse_bound(end, 25)?
+        } else if let Some((start, end)) = s.split_once("..") {
+            let start = parse_bound(start, 1)?;
+
+            if end.is_empty() {
+                start..=25
+            } else {
+                start..=parse_bound(end, 26)?.saturating_sub(1)
+            }
+        } else {
+            let day = parse_bound(s, 0)?;
+            day..=day
+        };
+
+        if range.is_empty() {
+            Err(anyhow!("Empty day range: '{}'", s))
+        } else {
+            Ok(DayRange(range))
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct RunAll {
+    /// Only run days in this range, e.g. '3', '1..=6' or '4..'
+    #[clap(long)]
+    pub days: Option<DayRange>,
+}
+
+#[derive(Debug)]
+pub struct RunResult {
+    pub day: Day,
+    pub part: Part,
+    pub input: InputSource,
+    pub parse_elapsed: Duration,
+    pub elapsed: Duration,
+    pub answer: Result<Answer>,
+}
+
+pub fn solve(day: Day, parts: &[Part], source: &InputSource, input: &str) -> Vec<RunResult> {
+    collect_results(day, parts, source, || day.run(parts, input))
+}
+
+fn collect_results<F>(day: Day, parts: &[Part], source: &InputSource, run: F) -> Vec<RunResult>
+where
+    F: FnOnce() -> Result<Run>,
+{
+    let span = tracing::info_span!("solve", day = day.name());
+    let _enter = span.enter();
+
+    let start = Instant::now();
+
+    match run() {
+        Ok(Run {
+            parse_elapsed,
+            parts: results,
+        }) => results
+            .into_iter()
+            .map(|part| RunResult {
+                day,
+                part: part.part,
+                input: source.clone(),
+                parse_elapsed,
+                elapsed: part.elapsed,
+                answer: part.answer,
+            })
+            .collect(),
+        Err(e) => {
+            let parse_elapsed = start.elapsed();
+
+            parts
+                .iter()
+                .map(|part| RunResult {
+                    day,
+                    part: *part,
+                    input: source.clone(),
+                    parse_elapsed,
+                    elapsed: Duration::default(),
+                    answer: Err(copy_error(&e, day, source)),
+                })
+                .collect()
+        }
+    }
+}
+
+/// anyhow errors can't be cloned, so each part gets a copy of the message, or of
+/// the `ParseError` with the day and input filled in
+fn copy_error(error: &anyhow::Error, day: Day, source: &InputSource) -> anyhow::Error {
+    match error.chain().find_map(|e| e.downcast_ref::<ParseError>()) {
+        Some(parse_error) => anyhow::Error::new(parse_error.clone().located(day, source)),
+        None => anyhow!("{:#}", error),
+    }
+}
+
+pub fn run_one(solution: &Solutions, source: &InputSource, format: Option<Format>) -> Result<()> {
+    let (day, part) = (solution.day(), solution.part());
+    let input = source.read(day)?;
+    let result = collect_results(day, &[part], source, || solution.run(&input))
+        .pop()
+        .ok_or_else(|| anyhow!("{} {} produced no result", day.name(), part))?;
+
+    match (format.unwrap_or(Format::Plain), result.answer) {
+        (Format::Plain, Err(e)) => Err(e),
+        (format, answer) => {
+            let failed = answer.is_err();
+            let result = RunResult { answer, ..result };
+
+            output::print_results(format, std::slice::from_ref(&result))?;
+
+            if failed {
+                Err(anyhow!("{} {} failed", day.name(), part))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn run_all(args: &RunAll, source: &InputSource, format: Option<Format>) -> Result<()> {
+    let days = args.days.clone().unwrap_or_default();
+    let mut results = Vec::new();
+
+    for day in Day::ALL.iter().filter(|day| days.contains(**day)) {
+        if !source.has_input(*day) {
+            eprintln!("{:<6} no example input", day.name());
+            continue;
+        }
+
+        let input = source.read(*day)?;
+
+        results.extend(solve(*day, &Part::ALL, source, &input));
+    }
+
+    output::print_results(format.unwrap_or(Format::Table), &results)?;
+
+    let failures = results.iter().filter(|r| r.answer.is_err()).count();
+
+    if failures > 0 {
+        Err(anyhow!(
+            "{} of {} solutions failed",
+            failures,
+            results.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_day_range() -> Result<()> {
+        assert_eq!("1..=6".parse::<DayRange>()?, DayRange(1..=6));
+        assert_eq!("2..5".parse::<DayRange>()?, DayRange(2..=4));
+        assert_eq!("4..".parse::<DayRange>()?, DayRange(4..=25));
+        assert_eq!("..=3".parse::<DayRange>()?, DayRange(1..=3));
+        assert_eq!("3".parse::<DayRange>()?, DayRange(3..=3));
+        assert!("5..=2".parse::<DayRange>().is_err());
+        assert!("x..=2".parse::<DayRange>().is_err());
+
+        assert!(DayRange(1..=6).contains(Day::day06));
+        assert!(!DayRange(1..=5).contains(Day::day06));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_parse_error() {
+        let results = solve(Day::day06, &Part::ALL, &InputSource::Stdin, "3,4,x");
+
+        assert_eq!(results.len(), 2);
+
+        for result in results {
+            let error = result.answer.unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+
+            assert_eq!(error.day, Some(Day::day06));
+            assert_eq!(error.input.as_deref(), Some("-"));
+            assert_eq!((error.line, error.column), (1, 5));
+        }
+    }
+}
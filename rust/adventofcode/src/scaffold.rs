@@ -0,0 +1,115 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{ensure, Context, Result};
+use clap::Parser;
+
+static SKELETON: &str = include_str!("../solution.rs.skel");
+
+#[derive(Debug, Parser)]
+pub struct NewDay {
+    /// The day number to create, 1 through 25
+    pub day: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Scaffolded {
+    pub module: PathBuf,
+    pub input: Option<PathBuf>,
+    pub example: Option<PathBuf>,
+}
+
+fn create_empty(path: PathBuf) -> Result<Option<PathBuf>> {
+    match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(_) => Ok(Some(path)),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Unable to create '{}'", path.display())),
+    }
+}
+
+/// Creates `src/dayNN.rs` from the skeleton and empty `inputs/dayNN.txt` and
+/// `inputs/examples/dayNN.txt` files under `root`.
+///
+/// An existing module is an error, existing input files are left alone.
+pub fn scaffold(root: &Path, day: u32) -> Result<Scaffolded> {
+    ensure!(
+        (1..=25).contains(&day),
+        "Day must be 1 through 25, not {}",
+        day
+    );
+
+    let name = format!("day{:02}", day);
+    let module = root.join("src").join(format!("{}.rs", name));
+    let inputs = root.join("inputs");
+
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&module)
+        .and_then(|mut file| file.write_all(SKELETON.replace("{{day}}", &name).as_bytes()))
+        .with_context(|| format!("Unable to create '{}'", module.display()))?;
+
+    fs::create_dir_all(inputs.join("examples"))?;
+
+    Ok(Scaffolded {
+        module,
+        input: create_empty(inputs.join(format!("{}.txt", name)))?,
+        example: create_empty(inputs.join("examples").join(format!("{}.txt", name)))?,
+    })
+}
+
+pub fn run_new_day(args: &NewDay) -> Result<()> {
+    let created = scaffold(Path::new(env!("CARGO_MANIFEST_DIR")), args.day)?;
+
+    println!("Created {}", created.module.display());
+
+    for path in created.input.iter().chain(created.example.iter()) {
+        println!("Created {}", path.display());
+    }
+
+    println!("Rebuild to register day{:02}", args.day);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_scaffold() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src"))?;
+        fs::create_dir_all(root.join("inputs"))?;
+
+        let created = scaffold(&root, 7)?;
+
+        assert_eq!(
+            created,
+            Scaffolded {
+                module: root.join("src/day07.rs"),
+                input: Some(root.join("inputs/day07.txt")),
+                example: Some(root.join("inputs/examples/day07.txt")),
+            }
+        );
+        assert!(fs::read_to_string(&created.module)?.contains("example(Day::day07)"));
+        assert_eq!(fs::read_to_string(root.join("inputs/day07.txt"))?, "");
+        assert_eq!(
+            fs::read_to_string(root.join("inputs/examples/day07.txt"))?,
+            ""
+        );
+
+        assert!(scaffold(&root, 7).is_err());
+        assert!(scaffold(&root, 26).is_err());
+
+        fs::remove_file(&created.module)?;
+        let created = scaffold(&root, 7)?;
+        assert_eq!(created.input, None);
+        assert_eq!(created.example, None);
+
+        fs::remove_dir_all(&root)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use clap::Parser;
+
+use crate::answer::Answer;
+use crate::command::Part;
+
+// For days without any options of their own. Not a doc comment, clap would
+// show it as the about text of every day using it.
+#[derive(Debug, Clone, Default, Parser)]
+pub struct NoOptions {}
+
+pub trait Solution {
+    type Input;
+
+    /// Extra command line arguments for the day's subcommand, used by `solve`
+    type Options: Default + fmt::Debug + clap::Args;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> Result<Answer>;
+
+    fn part_two(input: &Self::Input) -> Result<Answer>;
+
+    fn solve(input: &Self::Input, part: Part, _options: &Self::Options) -> Result<Answer> {
+        match part {
+            Part::One => Self::part_one(input),
+            Part::Two => Self::part_two(input),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: Part,
+    pub elapsed: Duration,
+    pub answer: Result<Answer>,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Parses `input` once and runs each of `parts` against the parsed result.
+pub fn run<S: Solution>(parts: &[Part], input: &str, options: &S::Options) -> Result<Run> {
+    let start = Instant::now();
+    let parsed = {
+        let span = tracing::debug_span!("parse");
+        let _enter = span.enter();
+
+        S::parse(input)?
+    };
+    let parse_elapsed = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|part| {
+            let span = tracing::debug_span!("solve", part = %part);
+            let _enter = span.enter();
+
+            let start = Instant::now();
+            let answer = S::solve(&parsed, *part, options);
+
+            PartRun {
+                part: *part,
+                elapsed: start.elapsed(),
+                answer,
+            }
+        })
+        .collect();
+
+    Ok(Run {
+        parse_elapsed,
+        parts,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u64>;
+        type Options = NoOptions;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input
+                .split(',')
+                .map(|n| n.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part_one(input: &Self::Input) -> Result<Answer> {
+            Ok(input.iter().sum::<u64>().into())
+        }
+
+        fn part_two(_input: &Self::Input) -> Result<Answer> {
+            Err(anyhow!("Not solved"))
+        }
+    }
+
+    #[test]
+    fn test_run() -> Result<()> {
+        let result = run::<Sum>(&Part::ALL, "1,2,3", &NoOptions {})?;
+
+        assert_eq!(result.parts.len(), 2);
+        assert_eq!(result.parts[0].part, Part::One);
+        assert_eq!(
+            result.parts[0].answer.as_ref().ok(),
+            Some(&Answer::from(6u64))
+        );
+        assert_eq!(result.parts[1].part, Part::Two);
+        assert!(result.parts[1].answer.is_err());
+
+        assert!(run::<Sum>(&Part::ALL, "1,x,3", &NoOptions {}).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{example, input, Day};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    #[default]
+    Embedded,
+    Example,
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Only false for `--example` when the day has no example yet, which `all` and
+    /// `verify` skip rather than fail on
+    pub fn has_input(&self, day: Day) -> bool {
+        *self != Self::Example || example(day).is_some()
+    }
+
+    pub fn read(&self, day: Day) -> Result<Cow<'static, str>> {
+        match self {
+            Self::Embedded => Ok(Cow::Borrowed(input(day))),
+            Self::Example => example(day)
+                .map(Cow::Borrowed)
+                .ok_or_else(|| anyhow!("{} has no example input", day.name())),
+            Self::Stdin => {
+                let mut buffer = String::new();
+
+                std::io::stdin()
+                    .read_to_string(&mut buffer)
+                    .context("Unable to read input from stdin")?;
+
+                Ok(Cow::Owned(buffer))
+            }
+            Self::File(path) => Ok(Cow::Owned(
+                std::fs::read_to_string(path)
+                    .with_context(|| format!("Unable to read input file '{}'", path.display()))?,
+            )),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => Self::Stdin,
+            path => Self::File(path.into()),
+        })
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Embedded => write!(f, "<embedded>"),
+            Self::Example => write!(f, "<example>"),
+            Self::Stdin => write!(f, "-"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_source() {
+        assert_eq!("-".parse::<InputSource>().unwrap(), InputSource::Stdin);
+        assert_eq!(
+            "inputs/day01.txt".parse::<InputSource>().unwrap(),
+            InputSource::File("inputs/day01.txt".into())
+        );
+    }
+
+    #[test]
+    fn test_read_embedded() -> Result<()> {
+        assert_eq!(
+            InputSource::Embedded.read(Day::day01)?,
+            Cow::Borrowed(input(Day::day01))
+        );
+        assert_eq!(
+            InputSource::Example.read(Day::day01)?,
+            Cow::Borrowed(example(Day::day01).unwrap())
+        );
+        Ok(())
+    }
+}
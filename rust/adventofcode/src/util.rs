@@ -0,0 +1,187 @@
+use std::convert::TryFrom;
+use std::iter::FusedIterator;
+
+/// Counts from `start` to `end` inclusive, downwards if `end` is below `start`.
+///
+/// Positions are `u128` because `i64::MIN..=i64::MAX` has one more value than fits in
+/// a `u64`. `ExactSizeIterator::len` panics if the length doesn't fit in a `usize`,
+/// `remaining` always works.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusiveRange {
+    start: i64,
+    step: i128,
+    front: u128,
+    back: u128,
+}
+
+#[allow(dead_code)]
+impl InclusiveRange {
+    pub fn new(start: i64, end: i64) -> Self {
+        Self {
+            start,
+            step: if end < start { -1 } else { 1 },
+            front: 0,
+            back: start.abs_diff(end) as u128 + 1,
+        }
+    }
+
+    pub fn remaining(&self) -> u128 {
+        self.back - self.front
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Takes every `step`th value, stopping at the last one that doesn't pass `end`.
+    /// Unlike `Iterator::step_by` the result is still an `InclusiveRange`.
+    pub fn step_by(self, step: u64) -> Self {
+        assert!(step != 0, "InclusiveRange step must be non-zero");
+
+        let len = self.remaining();
+        let step = step as u128;
+
+        Self {
+            start: self.value(self.front),
+            step: self.step * step as i128,
+            front: 0,
+            back: if len == 0 { 0 } else { (len - 1) / step + 1 },
+        }
+    }
+
+    fn value(&self, index: u128) -> i64 {
+        (self.start as i128 + self.step * index as i128) as i64
+    }
+}
+
+impl Iterator for InclusiveRange {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+
+        Some(self.value(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for InclusiveRange {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+
+        Some(self.value(self.back))
+    }
+}
+
+impl ExactSizeIterator for InclusiveRange {}
+
+impl FusedIterator for InclusiveRange {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_inclusive_range() {
+        assert_eq!(
+            InclusiveRange::new(1, 4).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            InclusiveRange::new(2, -2).collect::<Vec<_>>(),
+            vec![2, 1, 0, -1, -2]
+        );
+        assert_eq!(InclusiveRange::new(7, 7).collect::<Vec<_>>(), vec![7]);
+        assert_eq!(InclusiveRange::new(-3, 3).len(), 7);
+        assert_eq!(
+            InclusiveRange::new(i64::MAX - 1, i64::MAX).collect::<Vec<_>>(),
+            vec![i64::MAX - 1, i64::MAX]
+        );
+    }
+
+    #[test]
+    fn test_inclusive_range_double_ended() {
+        let mut range = InclusiveRange::new(5, 1);
+
+        assert_eq!(range.next_back(), Some(1));
+        assert_eq!(range.next(), Some(5));
+        assert_eq!(range.len(), 3);
+        assert_eq!(range.clone().rev().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(range.next_back(), Some(2));
+        assert_eq!(range.next_back(), Some(3));
+        assert_eq!(range.next(), Some(4));
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+        assert_eq!(range.len(), 0);
+    }
+
+    #[test]
+    fn test_inclusive_range_step_by() {
+        assert_eq!(
+            InclusiveRange::new(0, 10).step_by(3).collect::<Vec<_>>(),
+            vec![0, 3, 6, 9]
+        );
+        assert_eq!(
+            InclusiveRange::new(10, 0).step_by(5).collect::<Vec<_>>(),
+            vec![10, 5, 0]
+        );
+        assert_eq!(
+            InclusiveRange::new(0, 10)
+                .step_by(3)
+                .rev()
+                .collect::<Vec<_>>(),
+            vec![9, 6, 3, 0]
+        );
+        assert_eq!(InclusiveRange::new(0, 10).step_by(20).len(), 1);
+        assert_eq!(
+            InclusiveRange::new(0, 12)
+                .step_by(2)
+                .step_by(3)
+                .collect::<Vec<_>>(),
+            vec![0, 6, 12]
+        );
+
+        let mut range = InclusiveRange::new(0, 4);
+        range.next();
+        assert_eq!(range.step_by(2).collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_inclusive_range_full() {
+        let mut range = InclusiveRange::new(i64::MIN, i64::MAX);
+
+        assert_eq!(range.remaining(), u64::MAX as u128 + 1);
+        assert_eq!(range.size_hint(), (usize::MAX, None));
+        assert_eq!(range.next(), Some(i64::MIN));
+        assert_eq!(range.next_back(), Some(i64::MAX));
+        assert_eq!(range.remaining(), u64::MAX as u128 - 1);
+
+        let mut range = InclusiveRange::new(i64::MAX, i64::MIN).step_by(u64::MAX);
+
+        assert_eq!(range.len(), 2);
+        assert_eq!(range.size_hint(), (2, Some(2)));
+        assert_eq!(range.next(), Some(i64::MAX));
+        assert_eq!(range.next(), Some(i64::MIN));
+        assert_eq!(range.next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inclusive_range_step_zero() {
+        InclusiveRange::new(0, 1).step_by(0);
+    }
+}
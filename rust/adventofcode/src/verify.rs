@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+
+use crate::answer::Answer;
+use crate::command::Part;
+use crate::runner::{self, DayRange};
+use crate::source::InputSource;
+use crate::Day;
+
+static DEFAULT_ANSWERS: &str = include_str!("../../../inputs/answers.toml");
+static EXAMPLE_ANSWERS: &str = include_str!("../../../inputs/examples/answers.toml");
+
+#[derive(Debug, Parser)]
+pub struct Verify {
+    /// Only verify days in this range, e.g. '3', '1..=6' or '4..'
+    #[clap(long)]
+    pub days: Option<DayRange>,
+
+    /// Read the expected answers from this file instead of inputs/answers.toml
+    /// (or inputs/examples/answers.toml with --example)
+    #[clap(long)]
+    pub answers: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(HashMap<(String, String), Answer>);
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self> {
+        let table = s.parse::<toml::Value>()?;
+        let mut answers = HashMap::new();
+
+        for (day, parts) in table
+            .as_table()
+            .ok_or_else(|| anyhow!("Answers must be a table"))?
+        {
+            for (part, answer) in parts
+                .as_table()
+                .ok_or_else(|| anyhow!("Answers for '{}' must be a table", day))?
+            {
+                let answer = match answer {
+                    toml::Value::String(s) => Answer::from(s.as_str()),
+                    toml::Value::Integer(n) => Answer::from(*n),
+                    _ => bail!("Unsupported answer type for {} {}: {}", day, part, answer),
+                };
+
+                answers.insert((day.clone(), part.clone()), answer);
+            }
+        }
+
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: Day, part: Part) -> Option<&Answer> {
+        self.0.get(&(day.name().to_string(), part.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: Answer, actual: Answer },
+    Missing { actual: Answer },
+    Error(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Self::Missing { actual } => write!(f, "missing (got {})", actual),
+            Self::Error(e) => write!(f, "ERROR ({})", e),
+        }
+    }
+}
+
+fn check(expected: Option<&Answer>, actual: Result<Answer>) -> Status {
+    match (expected, actual) {
+        (_, Err(e)) => Status::Error(format!("{:#}", e)),
+        (None, Ok(actual)) => Status::Missing { actual },
+        (Some(expected), Ok(actual)) if *expected == actual => Status::Pass,
+        (Some(expected), Ok(actual)) => Status::Fail {
+            expected: expected.clone(),
+            actual,
+        },
+    }
+}
+
+pub fn run_verify(args: &Verify, source: &InputSource) -> Result<()> {
+    let answers = match &args.answers {
+        Some(path) => Answers::parse(
+            &std::fs::read_to_string(path)
+                .with_context(|| format!("Unable to read answers file '{}'", path.display()))?,
+        )?,
+        None if *source == InputSource::Example => Answers::parse(EXAMPLE_ANSWERS)?,
+        None => Answers::parse(DEFAULT_ANSWERS)?,
+    };
+
+    let days = args.days.clone().unwrap_or_default();
+    let mut failures = 0;
+
+    for day in Day::ALL.iter().filter(|day| days.contains(**day)) {
+        if !source.has_input(*day) {
+            println!("{:<6} no example input", day.name());
+            continue;
+        }
+
+        let input = source.read(*day)?;
+
+        for result in runner::solve(*day, &Part::ALL, source, &input) {
+            let status = check(answers.get(*day, result.part), result.answer);
+
+            if matches!(status, Status::Fail { .. } | Status::Error(_)) {
+                failures += 1;
+            }
+
+            println!("{:<6} {:<6} {}", day.name(), result.part, status);
+        }
+    }
+
+    if failures > 0 {
+        Err(anyhow!(
+            "{} solutions did not match their answers",
+            failures
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::example;
+
+    #[test]
+    fn test_parse_answers() -> Result<()> {
+        let answers = Answers::parse("[day02]\npart1 = 150\npart2 = \"#.\\n.#\"\n")?;
+
+        assert_eq!(
+            answers.get(Day::day02, Part::One),
+            Some(&Answer::from(150u64))
+        );
+        assert_eq!(
+            answers.get(Day::day02, Part::Two),
+            Some(&Answer::from(vec![String::from("#."), String::from(".#")]))
+        );
+        assert_eq!(answers.get(Day::day01, Part::One), None);
+
+        assert!(Answers::parse("day01 = 5").is_err());
+        assert!(Answers::parse("[day01]\npart1 = 1.5").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() {
+        let expected = Answer::from(150i64);
+
+        assert_eq!(
+            check(Some(&expected), Ok(Answer::from(150u64))),
+            Status::Pass
+        );
+        assert_eq!(
+            check(Some(&expected), Ok(Answer::from(151u64))),
+            Status::Fail {
+                expected: Answer::from(150i64),
+                actual: Answer::from(151u64),
+            }
+        );
+        assert_eq!(
+            check(None, Ok(Answer::from(151u64))),
+            Status::Missing {
+                actual: Answer::from(151u64),
+            }
+        );
+        assert_eq!(
+            check(Some(&expected), Err(anyhow!("No winning call"))),
+            Status::Error(String::from("No winning call"))
+        );
+    }
+
+    #[test]
+    fn test_default_answers() -> Result<()> {
+        let answers = Answers::parse(DEFAULT_ANSWERS)?;
+
+        assert!(answers.get(Day::day01, Part::One).is_some());
+
+        for (day, part) in answers.0.keys() {
+            day.parse::<Day>()?;
+            part.parse::<Part>()?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_examples() -> Result<()> {
+        let answers = Answers::parse(EXAMPLE_ANSWERS)?;
+
+        for day in Day::ALL.iter().filter(|day| example(**day).is_some()) {
+            let input = InputSource::Example.read(*day)?;
+
+            for result in runner::solve(*day, &Part::ALL, &InputSource::Example, &input) {
+                assert_eq!(
+                    check(answers.get(*day, result.part), result.answer),
+                    Status::Pass,
+                    "{} {}",
+                    day.name(),
+                    result.part
+                );
+            }
+        }
+
+        Ok(())
+    }
+}